@@ -0,0 +1,142 @@
+use std::fmt::{Debug, Formatter};
+use std::iter::FromIterator;
+
+use {Card, NUM_CARDS};
+
+// A set of cards, with one bit per card of the deck.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct CardSet {
+    // Bit n is set if the card with index n (see Card's Into<u8>) is in the set.
+    mask: u64,
+}
+
+impl CardSet {
+    pub fn empty() -> CardSet {
+        CardSet{mask: 0}
+    }
+
+    // All 52 cards.
+    pub fn full() -> CardSet {
+        CardSet{mask: (1 << NUM_CARDS) - 1}
+    }
+
+    // Returns whether the card was newly added.
+    pub fn insert(&mut self, card: Card) -> bool {
+        let was_present = self.contains(card);
+        self.mask |= bit_of(card);
+        !was_present
+    }
+
+    // Returns whether the card was there to remove.
+    pub fn remove(&mut self, card: Card) -> bool {
+        let was_present = self.contains(card);
+        self.mask &= !bit_of(card);
+        was_present
+    }
+
+    pub fn contains(&self, card: Card) -> bool {
+        self.mask & bit_of(card) != 0
+    }
+
+    pub fn union(&self, other: &CardSet) -> CardSet {
+        CardSet{mask: self.mask | other.mask}
+    }
+
+    pub fn intersection(&self, other: &CardSet) -> CardSet {
+        CardSet{mask: self.mask & other.mask}
+    }
+
+    // The cards in this set which aren't in the other one.
+    pub fn difference(&self, other: &CardSet) -> CardSet {
+        CardSet{mask: self.mask & !other.mask}
+    }
+
+    pub fn len(&self) -> usize {
+        self.mask.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mask == 0
+    }
+
+    // Goes through the cards in ascending order of index.
+    pub fn iter(&self) -> CardSetIter {
+        CardSetIter{remaining: self.mask}
+    }
+}
+
+fn bit_of(card: Card) -> u64 {
+    let index: u8 = card.into();
+    1 << index
+}
+
+pub struct CardSetIter {
+    remaining: u64,
+}
+
+impl Iterator for CardSetIter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let index = self.remaining.trailing_zeros() as u8;
+        // Clear the lowest bit.
+        self.remaining &= self.remaining - 1;
+        Some(Card::from(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item=Card>>(cards: I) -> CardSet {
+        let mut set = CardSet::empty();
+        set.extend(cards);
+        set
+    }
+}
+
+impl Extend<Card> for CardSet {
+    fn extend<I: IntoIterator<Item=Card>>(&mut self, cards: I) {
+        for card in cards {
+            self.insert(card);
+        }
+    }
+}
+
+impl<'a> Extend<&'a Card> for CardSet {
+    fn extend<I: IntoIterator<Item=&'a Card>>(&mut self, cards: I) {
+        for card in cards {
+            self.insert(*card);
+        }
+    }
+}
+
+impl Debug for CardSet {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
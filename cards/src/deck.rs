@@ -0,0 +1,79 @@
+use rand::Rng;
+
+use {Card, CardSet};
+
+/*
+A deck to deal from without replacement.
+
+Dealt and burned cards stay in the deck's storage, behind a marker for the
+top of the deck, so that reset() can put them back without allocating.
+This makes it cheap to reuse one deck for many hands.
+*/
+#[derive(Clone, Debug)]
+pub struct Deck {
+    cards: Vec<Card>,
+    // Index of the top of the deck. Everything before it has been dealt.
+    num_dealt: usize,
+}
+
+impl Deck {
+    // All 52 cards, in index order.
+    pub fn full() -> Deck {
+        Deck::without(&CardSet::empty())
+    }
+
+    // All cards except for the dead ones, in index order.
+    pub fn without(dead_cards: &CardSet) -> Deck {
+        let cards = CardSet::full().difference(dead_cards).iter().collect();
+        Deck{cards, num_dealt: 0}
+    }
+
+    // Shuffles the cards which haven't been dealt yet.
+    pub fn shuffle<R: Rng>(&mut self, rng: &mut R) {
+        rng.shuffle(&mut self.cards[self.num_dealt..]);
+    }
+
+    // Deals the top card, if there are any left.
+    pub fn deal(&mut self) -> Option<Card> {
+        if self.is_empty() {
+            return None;
+        }
+        let card = self.cards[self.num_dealt];
+        self.num_dealt += 1;
+        Some(card)
+    }
+
+    // Deals a card picked at random from what's left, which is cheaper than shuffling to deal a few cards.
+    pub fn deal_random<R: Rng>(&mut self, rng: &mut R) -> Option<Card> {
+        if self.is_empty() {
+            return None;
+        }
+        let index = rng.gen_range(self.num_dealt, self.cards.len());
+        self.cards.swap(self.num_dealt, index);
+        self.deal()
+    }
+
+    // Throws away the top card. Returns it in case anyone's curious.
+    pub fn burn(&mut self) -> Option<Card> {
+        self.deal()
+    }
+
+    // Puts every dealt or burned card back. The order of the deck isn't restored.
+    pub fn reset(&mut self) {
+        self.num_dealt = 0;
+    }
+
+    // How many cards are left to deal.
+    pub fn len(&self) -> usize {
+        self.cards.len() - self.num_dealt
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // The cards left to deal, top first.
+    pub fn remaining(&self) -> &[Card] {
+        &self.cards[self.num_dealt..]
+    }
+}
@@ -5,7 +5,16 @@ use std::cmp::{Ord, PartialOrd, Ordering};
 use std::fmt::{Debug, Formatter};
 use rand::{Rng, Rand};
 
+// The tests predate field init shorthand.
+#[allow(clippy::redundant_field_names)]
 mod tests;
+mod card_set;
+mod deck;
+mod notation;
+
+pub use card_set::CardSet;
+pub use deck::Deck;
+pub use notation::{ParseError, parse_cards};
 
 #[derive(Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Debug)]
 pub enum Suit {
@@ -109,7 +118,7 @@ impl Ord for Card {
         if rank_ordering != Ordering::Equal {
             return rank_ordering
         }
-        return self.suit.cmp(&other.suit)
+        self.suit.cmp(&other.suit)
     }
 }
 
@@ -126,20 +135,28 @@ impl PartialEq for Card {
 }
 
 pub fn card(rank: Rank, suit: Suit) -> Card {
-    Card{rank: rank, suit: suit}
+    Card{rank, suit}
 }
 
+pub const NUM_CARDS: u8 = 52;
+
 impl Rand for Card {
     fn rand<R: Rng>(rng: &mut R) -> Self {
-        let index = rng.gen_range(0, 52);
-        let suit: Suit = Suit::from(index % 4);
-        let rank: Rank = Rank::from(index / 4);
-        card(rank, suit)
+        let index: u8 = rng.gen_range(0, NUM_CARDS);
+        Card::from(index)
+    }
+}
+
+impl From<Card> for u8 {
+    fn from(card: Card) -> Self {
+        ((card.rank as u8) * 4) + (card.suit as u8)
     }
 }
 
-impl Into<u8> for Card {
-    fn into(self) -> u8 {
-        ((self.rank as u8) * 4) + (self.suit as u8)
+impl From<u8> for Card {
+    fn from(index: u8) -> Self {
+        let suit: Suit = Suit::from(index % 4);
+        let rank: Rank = Rank::from(index / 4);
+        card(rank, suit)
     }
 }
@@ -0,0 +1,175 @@
+/*
+The usual short notation for cards: a rank character followed by a suit
+character, like "As" or "Td". Ranks are A, K, Q, J, T and 9 through 2;
+suits are s, h, c and d. Parsing ignores case, and formatting uses upper
+case ranks and lower case suits.
+*/
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use {Card, Rank, Suit, card};
+use Rank::*;
+use Suit::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ParseError {
+    // Positions count characters from the start of the input.
+    InvalidRank{position: usize, found: char},
+    InvalidSuit{position: usize, found: char},
+    // The input ended after a rank, at the position where the suit should be.
+    MissingSuit{position: usize},
+    // Something which should be a single rank, suit or card had the wrong number of characters.
+    WrongLength{expected: usize, found: usize},
+    // There was only whitespace where a card should be.
+    Empty,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match *self {
+            ParseError::InvalidRank{position, found} =>
+                write!(f, "'{}' at position {} is not a rank", found, position),
+            ParseError::InvalidSuit{position, found} =>
+                write!(f, "'{}' at position {} is not a suit", found, position),
+            ParseError::MissingSuit{position} =>
+                write!(f, "missing a suit at position {}", position),
+            ParseError::WrongLength{expected, found} =>
+                write!(f, "expected {} characters but found {}", expected, found),
+            ParseError::Empty => write!(f, "no card found"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+fn rank_from_char(rank_char: char) -> Option<Rank> {
+    let rank = match rank_char {
+        'A' | 'a' => Ace,
+        'K' | 'k' => King,
+        'Q' | 'q' => Queen,
+        'J' | 'j' => Jack,
+        'T' | 't' => Ten,
+        '9' => Nine,
+        '8' => Eight,
+        '7' => Seven,
+        '6' => Six,
+        '5' => Five,
+        '4' => Four,
+        '3' => Three,
+        '2' => Two,
+        _ => return None
+    };
+    Some(rank)
+}
+
+fn suit_from_char(suit_char: char) -> Option<Suit> {
+    let suit = match suit_char {
+        'S' | 's' => Spades,
+        'H' | 'h' => Hearts,
+        'C' | 'c' => Clubs,
+        'D' | 'd' => Diamonds,
+        _ => return None
+    };
+    Some(suit)
+}
+
+fn single_char(string: &str) -> Result<char, ParseError> {
+    let chars: Vec<char> = string.chars().collect();
+    if chars.len() != 1 {
+        return Err(ParseError::WrongLength{expected: 1, found: chars.len()});
+    }
+    Ok(chars[0])
+}
+
+impl FromStr for Rank {
+    type Err = ParseError;
+
+    fn from_str(string: &str) -> Result<Rank, ParseError> {
+        let rank_char = single_char(string)?;
+        rank_from_char(rank_char).ok_or(ParseError::InvalidRank{position: 0, found: rank_char})
+    }
+}
+
+impl FromStr for Suit {
+    type Err = ParseError;
+
+    fn from_str(string: &str) -> Result<Suit, ParseError> {
+        let suit_char = single_char(string)?;
+        suit_from_char(suit_char).ok_or(ParseError::InvalidSuit{position: 0, found: suit_char})
+    }
+}
+
+impl FromStr for Card {
+    type Err = ParseError;
+
+    fn from_str(string: &str) -> Result<Card, ParseError> {
+        let num_chars = string.chars().count();
+        if num_chars != 2 {
+            return Err(ParseError::WrongLength{expected: 2, found: num_chars});
+        }
+        // Two characters of whitespace have the right length, but no card.
+        let cards = parse_cards(string)?;
+        cards.first().cloned().ok_or(ParseError::Empty)
+    }
+}
+
+// Parses any number of cards written back to back, like "AsKd7c". Whitespace between cards is ignored.
+pub fn parse_cards(cards_string: &str) -> Result<Vec<Card>, ParseError> {
+    let mut cards = Vec::with_capacity(cards_string.len() / 2);
+    let mut chars = cards_string.chars().enumerate();
+    while let Some((rank_position, rank_char)) = chars.next() {
+        if rank_char.is_whitespace() {
+            continue;
+        }
+        let rank = rank_from_char(rank_char).ok_or(
+            ParseError::InvalidRank{position: rank_position, found: rank_char})?;
+        let suit = match chars.next() {
+            Some((suit_position, suit_char)) => suit_from_char(suit_char).ok_or(
+                ParseError::InvalidSuit{position: suit_position, found: suit_char})?,
+            None => return Err(ParseError::MissingSuit{position: rank_position + 1}),
+        };
+        cards.push(card(rank, suit));
+    }
+    Ok(cards)
+}
+
+impl Display for Rank {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let rank_char = match *self {
+            Ace => 'A',
+            King => 'K',
+            Queen => 'Q',
+            Jack => 'J',
+            Ten => 'T',
+            Nine => '9',
+            Eight => '8',
+            Seven => '7',
+            Six => '6',
+            Five => '5',
+            Four => '4',
+            Three => '3',
+            Two => '2',
+        };
+        write!(f, "{}", rank_char)
+    }
+}
+
+impl Display for Suit {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let suit_char = match *self {
+            Spades => 's',
+            Hearts => 'h',
+            Clubs => 'c',
+            Diamonds => 'd',
+        };
+        write!(f, "{}", suit_char)
+    }
+}
+
+impl Display for Card {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}{}", self.rank, self.suit)
+    }
+}
@@ -41,3 +41,117 @@ fn all_cards_of_rank(rank: Rank) -> [Card; 4] {
      Card{rank: rank, suit: Suit::Diamonds},
      Card{rank: rank, suit: Suit::Clubs}]
 }
+
+#[test]
+fn card_indices_round_trip() {
+    for index in 0..NUM_CARDS {
+        let card = Card::from(index);
+        let card_index: u8 = card.into();
+        assert_eq!(index, card_index);
+    }
+}
+
+#[test]
+fn card_set_membership() {
+    let mut set = CardSet::empty();
+    assert!(set.is_empty());
+    assert!(set.insert(card(Ace, Spades)));
+    assert!(!set.insert(card(Ace, Spades)));
+    assert!(set.insert(card(Two, Diamonds)));
+    assert_eq!(2, set.len());
+    assert!(set.contains(card(Ace, Spades)));
+    assert!(!set.contains(card(Ace, Hearts)));
+
+    assert!(set.remove(card(Ace, Spades)));
+    assert!(!set.remove(card(Ace, Spades)));
+    assert_eq!(1, set.len());
+    assert!(!set.contains(card(Ace, Spades)));
+}
+
+#[test]
+fn card_set_operations() {
+    let first: CardSet = vec![card(Ace, Spades), card(King, Hearts), card(Two, Clubs)].into_iter().collect();
+    let second: CardSet = vec![card(King, Hearts), card(Three, Clubs)].into_iter().collect();
+
+    assert_eq!(4, first.union(&second).len());
+    let both: Vec<Card> = first.intersection(&second).iter().collect();
+    assert_eq!(vec![card(King, Hearts)], both);
+    let only_first: Vec<Card> = first.difference(&second).iter().collect();
+    assert_eq!(vec![card(Two, Clubs), card(Ace, Spades)], only_first);
+}
+
+#[test]
+fn full_card_set() {
+    let full = CardSet::full();
+    assert_eq!(52, full.len());
+    let cards: Vec<Card> = full.iter().collect();
+    for (index, card) in cards.iter().enumerate() {
+        assert_eq!(Card::from(index as u8), *card);
+    }
+}
+
+#[test]
+fn deal_whole_deck() {
+    let mut deck = Deck::full();
+    let mut dealt = CardSet::empty();
+    while let Some(card) = deck.deal() {
+        assert!(dealt.insert(card), "{:?} dealt twice", card);
+    }
+    assert_eq!(52, dealt.len());
+    assert!(deck.is_empty());
+    assert_eq!(None, deck.burn());
+}
+
+#[test]
+fn deck_without_dead_cards() {
+    let dead: CardSet = vec![card(Ace, Spades), card(Seven, Diamonds)].into_iter().collect();
+    let mut deck = Deck::without(&dead);
+    assert_eq!(50, deck.len());
+
+    let mut rng = rand::thread_rng();
+    deck.shuffle(&mut rng);
+    assert!(deck.burn().is_some());
+    let mut dealt = CardSet::empty();
+    while let Some(card) = deck.deal_random(&mut rng) {
+        assert!(!dead.contains(card));
+        assert!(dealt.insert(card), "{:?} dealt twice", card);
+    }
+    assert_eq!(49, dealt.len());
+
+    deck.reset();
+    assert_eq!(50, deck.len());
+    let remaining: CardSet = deck.remaining().iter().cloned().collect();
+    assert_eq!(CardSet::full().difference(&dead), remaining);
+}
+
+#[test]
+fn parse_and_display_cards() {
+    for index in 0..NUM_CARDS {
+        let card = Card::from(index);
+        let card_string = card.to_string();
+        assert_eq!(Ok(card), card_string.parse());
+        assert_eq!(Ok(card), card_string.to_lowercase().parse());
+    }
+    assert_eq!("Td", card(Ten, Diamonds).to_string());
+    assert_eq!(Ok(Queen), "q".parse());
+    assert_eq!(Ok(Clubs), "C".parse());
+}
+
+#[test]
+fn parse_card_lists() {
+    assert_eq!(Ok(vec![card(Ace, Spades), card(Ten, Diamonds), card(Two, Clubs)]), parse_cards("AsTd2c"));
+    assert_eq!(Ok(vec![card(King, Hearts), card(Nine, Spades)]), parse_cards("kH 9s"));
+    assert_eq!(Ok(Vec::new()), parse_cards(""));
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(Err(ParseError::InvalidRank{position: 2, found: 'X'}), parse_cards("AsXd"));
+    assert_eq!(Err(ParseError::InvalidSuit{position: 3, found: 'x'}), parse_cards("AsKx"));
+    assert_eq!(Err(ParseError::MissingSuit{position: 3}), parse_cards("AsK"));
+    assert_eq!(Err(ParseError::WrongLength{expected: 2, found: 4}), "AsKd".parse::<Card>());
+    assert_eq!(Err(ParseError::Empty), "  ".parse::<Card>());
+    assert_eq!(Err(ParseError::MissingSuit{position: 2}), " A".parse::<Card>());
+    assert_eq!(Err(ParseError::WrongLength{expected: 1, found: 0}), "".parse::<Rank>());
+    assert_eq!(Err(ParseError::InvalidSuit{position: 0, found: 'x'}), "x".parse::<Suit>());
+}
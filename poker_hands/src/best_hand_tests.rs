@@ -1,24 +1,20 @@
 #![cfg(test)]
 
-use std::fmt::Debug;
 use super::*;
 use super::Hand::*;
+use test_helpers::assert_equal;
 
 use cards::Card;
 use cards::Rank::*;
 use cards::Suit::*;
 use cards::card;
 
-fn assert_equal<T: Eq + Debug>(expected: &T, actual: &T) {
-    assert!(expected == actual, "Expected {:?} but got {:?}", expected, actual)
-}
-
 fn assert_best_hand(cards: &[Card], expected_hand: Hand) {
-    for index in 1..cards.len() {
-        assert!(cards[index].rank <= cards[index - 1].rank);
-    }
-    let actual_hand = Hand::best_hand_of(cards);
+    let actual_hand = Hand::best_hand_of(cards).unwrap();
     assert_equal(&expected_hand, &actual_hand);
+    // The order of the cards shouldn't matter.
+    let reversed_hand = Hand::best_hand_of(cards.iter().rev()).unwrap();
+    assert_equal(&expected_hand, &reversed_hand);
 }
 
 #[test]
@@ -146,3 +142,47 @@ fn find_air() {
     cards = [card(Jack, Spades), card(Nine, Diamonds), card(Seven, Spades), card(Six, Hearts), card(Four, Spades), card(Three, Clubs), card(Two, Clubs)];
     assert_best_hand(&cards, HiCard(HiCardStr{ranks: [Jack, Nine, Seven, Six, Four]}));
 }
+
+#[test]
+fn reject_invalid_cards() {
+    let too_few = [card(Ace, Spades), card(King, Spades), card(Queen, Spades), card(Jack, Spades)];
+    assert_equal(&Err(InvalidHandError::TooFewCards{needed: 5, found: 4}), &Hand::best_hand_of(too_few));
+
+    let repeated = [card(Ace, Spades), card(King, Spades), card(King, Spades), card(Queen, Spades), card(Jack, Spades), card(Ten, Spades)];
+    assert_equal(&Err(InvalidHandError::DuplicateCard(card(King, Spades))), &Hand::best_hand_of(repeated));
+
+    let too_many = [card(Ace, Spades), card(King, Spades), card(Queen, Spades), card(Jack, Spades), card(Ten, Spades), card(Nine, Spades)];
+    assert_equal(&Err(InvalidHandError::WrongNumberOfCards{expected: 5, found: 6}), &Hand::get_hand(too_many));
+}
+
+#[test]
+fn cards_in_any_order() {
+    let cards = [card(Two, Hearts), card(Jack, Spades), card(Ace, Spades), card(Ten, Spades), card(Three, Clubs), card(King, Spades), card(Queen, Spades)];
+    assert_equal(&StraightFlush(StraightFlushStr{hi_rank: Ace}), &Hand::best_hand_of(cards).unwrap());
+
+    let card_set: CardSet = cards.iter().cloned().collect();
+    assert_equal(&StraightFlush(StraightFlushStr{hi_rank: Ace}), &Hand::best_hand_of(card_set).unwrap());
+
+    let five_cards = vec![card(Four, Hearts), card(Ace, Clubs), card(Three, Spades), card(Five, Diamonds), card(Two, Clubs)];
+    assert_equal(&Straight(StraightStr{hi_rank: Five}), &Hand::get_hand(five_cards).unwrap());
+}
+
+#[test]
+fn report_cards_used() {
+    let hole_cards = [card(Ace, Spades), card(Seven, Hearts)];
+    let board = [card(Ace, Hearts), card(King, Clubs), card(Queen, Diamonds), card(Two, Spades), card(Three, Clubs)];
+    let all_cards: Vec<Card> = hole_cards.iter().chain(&board).cloned().collect();
+    let best_hand = Hand::best_hand_with_cards(&all_cards).unwrap();
+    assert_equal(&Pair(PairStr{rank: Ace, kickers: [King, Queen, Seven]}), &best_hand.hand);
+    assert_eq!([card(Ace, Spades), card(Ace, Hearts), card(King, Clubs), card(Queen, Diamonds), card(Seven, Hearts)], best_hand.cards);
+    assert_eq!(2, best_hand.num_used_from(&hole_cards));
+    assert!(!best_hand.uses(card(Two, Spades)));
+
+    // A straight on the board that nothing in the hole improves on.
+    let hole_cards = [card(Two, Hearts), card(Two, Diamonds)];
+    let board = [card(Nine, Spades), card(Eight, Clubs), card(Seven, Hearts), card(Six, Spades), card(Five, Diamonds)];
+    let best_hand = Hand::best_hand_with_cards(hole_cards.iter().chain(&board)).unwrap();
+    assert_equal(&Straight(StraightStr{hi_rank: Nine}), &best_hand.hand);
+    assert_eq!(board, best_hand.cards);
+    assert_eq!(0, best_hand.num_used_from(&hole_cards));
+}
@@ -0,0 +1,65 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use cards::{Card, Rank};
+
+use NUM_HANDS;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum InvalidHandError {
+    // There need to be at least this many cards to pick from.
+    TooFewCards{needed: usize, found: usize},
+    // There can be at most this many cards to pick from.
+    TooManyCards{allowed: usize, found: usize},
+    // A hand is made from exactly this many cards.
+    WrongNumberOfCards{expected: usize, found: usize},
+    // The same card appears more than once.
+    DuplicateCard(Card),
+    // The card isn't dealt under the rules being played.
+    NotInDeck(Card),
+    // A number which isn't one of the evaluator's hand ranks.
+    NotAHandRank(u16),
+}
+
+impl Display for InvalidHandError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match *self {
+            InvalidHandError::TooFewCards{needed, found} =>
+                write!(f, "needed at least {} cards but found {}", needed, found),
+            InvalidHandError::TooManyCards{allowed, found} =>
+                write!(f, "expected at most {} cards but found {}", allowed, found),
+            InvalidHandError::WrongNumberOfCards{expected, found} =>
+                write!(f, "expected {} cards but found {}", expected, found),
+            InvalidHandError::DuplicateCard(card) =>
+                write!(f, "{} appears more than once", card),
+            InvalidHandError::NotInDeck(card) =>
+                write!(f, "{} isn't in the deck", card),
+            InvalidHandError::NotAHandRank(rank) =>
+                write!(f, "{} isn't a hand rank", rank),
+        }
+    }
+}
+
+impl Error for InvalidHandError {}
+
+// Reasons a set of rules can't be played.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum InvalidRulesError {
+    // The category order doesn't have each category exactly once.
+    NotACategoryOrder([u8; NUM_HANDS]),
+    // With this as the lowest rank, the ace-low straight would be the same as the ace-high one, or there wouldn't be one.
+    TooFewRanks(Rank),
+}
+
+impl Display for InvalidRulesError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match *self {
+            InvalidRulesError::NotACategoryOrder(order) =>
+                write!(f, "{:?} doesn't list each hand category exactly once", order),
+            InvalidRulesError::TooFewRanks(rank) =>
+                write!(f, "a deck starting at {:?} doesn't have enough ranks", rank),
+        }
+    }
+}
+
+impl Error for InvalidRulesError {}
@@ -0,0 +1,444 @@
+/*
+Fast evaluation of 5 to 7 cards into a single number.
+
+Every distinct five-card hand falls into one of 7462 equivalence classes,
+numbered from 1 (a royal flush) to 7462 (seven-five high). Lower numbers
+are better hands, and hands with the same number tie.
+
+Rather than trying all five-card subsets, the cards are boiled down to
+bitmasks of ranks, one per suit and one per number of copies of a rank.
+A flush can be read straight off a suit mask, and everything else falls
+out of the rank masks. The ranks within each category are numbered by
+table lookups (for five different ranks) or by counting combinations
+(for everything with a pair or better in it).
+
+Other rules, like short deck, are evaluated the same way with their own
+wheel, and then each category's ranks are moved to where the rules put
+that category. So the ranks still run from 1 to 7462, but some of them
+can't come up. That only finds the best hand when the rules keep the
+standard order between any two categories that the cards could make
+together; for other orders, every five-card subset is tried instead.
+*/
+
+use std::sync::OnceLock;
+
+use cards::{Card, Rank, Suit, card};
+use {Hand, InvalidHandError, Rules, NUM_HANDS, check_for_duplicates};
+
+pub const NUM_HAND_RANKS: u16 = 7462;
+
+// The best rank of each category. Each category runs until the next one starts.
+const STRAIGHT_FLUSH_START: u16 = 1;
+const QUADS_START: u16 = 11;
+const FULL_HOUSE_START: u16 = 167;
+const FLUSH_START: u16 = 323;
+const STRAIGHT_START: u16 = 1600;
+const TRIPS_START: u16 = 1610;
+const TWO_PAIR_START: u16 = 2468;
+const PAIR_START: u16 = 3326;
+const HI_CARD_START: u16 = 6186;
+
+// The best rank of each category, and how many ranks it has, indexed like Hand's Into<u8>.
+const CATEGORY_STARTS: [u16; NUM_HANDS] = [HI_CARD_START, PAIR_START, TWO_PAIR_START, TRIPS_START, STRAIGHT_START, FLUSH_START, FULL_HOUSE_START, QUADS_START, STRAIGHT_FLUSH_START];
+const CATEGORY_SIZES: [u16; NUM_HANDS] = [1277, 2860, 858, 858, 10, 1277, 156, 156, 10];
+
+const NUM_RANKS: u16 = 13;
+const ALL_RANKS_MASK: u16 = (1 << NUM_RANKS) - 1;
+const NO_STRAIGHT: u8 = 0xFF;
+
+struct Tables {
+    // For each mask of exactly five ranks that isn't a straight, how many such masks are better.
+    five_rank_index: Vec<u16>,
+    // For each rank mask, the rank index of the top card of the best straight in it, if any.
+    straight_top: Vec<u8>,
+}
+
+fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+    TABLES.get_or_init(build_tables)
+}
+
+fn build_tables() -> Tables {
+    let mut straight_top = vec![NO_STRAIGHT; ALL_RANKS_MASK as usize + 1];
+    for mask in 0..=ALL_RANKS_MASK {
+        straight_top[mask as usize] = find_straight_top(mask);
+    }
+
+    // Going down from the highest mask visits five-rank hands from best to worst.
+    let mut five_rank_index = vec![0; ALL_RANKS_MASK as usize + 1];
+    let mut num_better = 0;
+    for mask in (0..=ALL_RANKS_MASK).rev() {
+        if mask.count_ones() != 5 || straight_top[mask as usize] != NO_STRAIGHT {
+            continue;
+        }
+        five_rank_index[mask as usize] = num_better;
+        num_better += 1;
+    }
+    assert_eq!(1277, num_better);
+
+    Tables{five_rank_index, straight_top}
+}
+
+fn find_straight_top(mask: u16) -> u8 {
+    for top in (4..NUM_RANKS).rev() {
+        let straight = 0x1F << (top - 4);
+        if mask & straight == straight {
+            return top as u8;
+        }
+    }
+    // The wheel: ace, five, four, three, two.
+    let wheel = (1 << Rank::Ace as u16) | 0xF;
+    if mask & wheel == wheel {
+        return Rank::Five as u8;
+    }
+    NO_STRAIGHT
+}
+
+pub const MIN_CARDS: usize = 5;
+pub const MAX_CARDS: usize = 7;
+
+// Evaluates the best five-card hand out of 5 to 7 different cards, in any order.
+pub fn evaluate(cards: &[Card]) -> Result<u16, InvalidHandError> {
+    check_cards(cards)?;
+    Ok(evaluate_with_wheel(cards, Wheel::of(Rank::Five)))
+}
+
+/*
+Like evaluate, but under the given rules. Only ranks under the same rules
+can be compared. Cards that aren't in the rules' deck are an error.
+*/
+pub fn evaluate_with_rules(cards: &[Card], rules: &Rules) -> Result<u16, InvalidHandError> {
+    check_cards(cards)?;
+    rules.check_in_deck(cards)?;
+    Ok(evaluate_checked_with_rules(cards, rules))
+}
+
+// Like evaluate_with_rules, for cards the caller has already checked.
+pub(crate) fn evaluate_checked_with_rules(cards: &[Card], rules: &Rules) -> u16 {
+    let wheel = Wheel::of(rules.wheel_top());
+    if cards.len() == MIN_CARDS || standard_order_finds_best(rules) {
+        return to_rules_rank(evaluate_with_wheel(cards, wheel), rules);
+    }
+    (0..(1u32 << cards.len()))
+        .filter(|used| used.count_ones() == MIN_CARDS as u32)
+        .map(|used| {
+            let mut five_cards = [cards[0]; MIN_CARDS];
+            let used_cards = cards.iter().enumerate().filter(|&(index, _)| used & (1 << index) != 0);
+            for (slot, (_, &card)) in five_cards.iter_mut().zip(used_cards) {
+                *slot = card;
+            }
+            to_rules_rank(evaluate_with_wheel(&five_cards, wheel), rules)
+        })
+        .min()
+        .unwrap()
+}
+
+// Pairs of categories, the better one in the standard order first, that no 7 cards can make both of.
+const NEVER_TOGETHER: [(u8, u8); 6] = [(8, 7), (8, 6), (7, 5), (7, 4), (6, 5), (6, 4)];
+
+/*
+Whether the best category in the standard order is also the best under
+the rules, for any 5 to 7 cards. That holds unless the rules swap two
+categories that the same cards can make, like trips and a straight.
+*/
+fn standard_order_finds_best(rules: &Rules) -> bool {
+    let order = rules.category_order();
+    (0..NUM_HANDS).all(|worse| ((worse + 1)..NUM_HANDS).all(|better| {
+        order[worse] < order[better] || NEVER_TOGETHER.contains(&(order[worse], order[better]))
+    }))
+}
+
+fn to_rules_rank(standard_rank: u16, rules: &Rules) -> u16 {
+    let category = standard_category(standard_rank);
+    rules_category_start(category, rules) + (standard_rank - CATEGORY_STARTS[category as usize])
+}
+
+fn check_cards(cards: &[Card]) -> Result<(), InvalidHandError> {
+    if cards.len() < MIN_CARDS {
+        return Err(InvalidHandError::TooFewCards{needed: MIN_CARDS, found: cards.len()});
+    }
+    if cards.len() > MAX_CARDS {
+        return Err(InvalidHandError::TooManyCards{allowed: MAX_CARDS, found: cards.len()});
+    }
+    check_for_duplicates(cards)
+}
+
+fn check_rank(rank: u16) -> Result<(), InvalidHandError> {
+    if (1..=NUM_HAND_RANKS).contains(&rank) {
+        Ok(())
+    } else {
+        Err(InvalidHandError::NotAHandRank(rank))
+    }
+}
+
+// The straight where the ace plays low.
+#[derive(Copy, Clone)]
+struct Wheel {
+    // The ace and the four ranks under the top.
+    mask: u16,
+    top: u8,
+}
+
+impl Wheel {
+    fn of(top: Rank) -> Wheel {
+        let top = top as u8;
+        Wheel{mask: (1 << Rank::Ace as u16) | (0xF << (top - 3)), top}
+    }
+
+    // The rank index of the top card of the best straight in the mask, if any.
+    fn straight_top(&self, mask: u16) -> u8 {
+        let straight_top = tables().straight_top[mask as usize];
+        if straight_top == NO_STRAIGHT && mask & self.mask == self.mask {
+            return self.top;
+        }
+        straight_top
+    }
+}
+
+// The cards must already be checked.
+fn evaluate_with_wheel(cards: &[Card], wheel: Wheel) -> u16 {
+    debug_assert!((MIN_CARDS..=MAX_CARDS).contains(&cards.len()));
+
+    let mut suit_masks = [0u16; 4];
+    let mut rank_counts = [0u8; NUM_RANKS as usize];
+    for card in cards {
+        suit_masks[card.suit as usize] |= 1 << card.rank as u16;
+        rank_counts[card.rank as usize] += 1;
+    }
+
+    // With at most 7 cards, a flush rules out quads and full houses, so it's the best there is.
+    for &suit_mask in &suit_masks {
+        if suit_mask.count_ones() >= 5 {
+            return evaluate_flush(suit_mask, wheel);
+        }
+    }
+
+    // Masks of the ranks that appear exactly once, twice, three or four times.
+    let mut count_masks = [0u16; 5];
+    for (rank, &count) in rank_counts.iter().enumerate() {
+        count_masks[count as usize] |= 1 << rank;
+    }
+    let singles = count_masks[1];
+    let pairs = count_masks[2];
+    let trips = count_masks[3];
+    let quads = count_masks[4];
+    let all_ranks = singles | pairs | trips | quads;
+
+    if quads != 0 {
+        let quad_rank = top_rank(quads);
+        let kicker = top_rank(all_ranks & !(1 << quad_rank));
+        return QUADS_START + (12 - quad_rank) * 12 + subset_index(compress(1 << kicker, quad_rank), 12, 1);
+    }
+
+    if trips != 0 {
+        let trip_rank = top_rank(trips);
+        let others = (trips | pairs) & !(1 << trip_rank);
+        if others != 0 {
+            let pair_rank = top_rank(others);
+            return FULL_HOUSE_START + (12 - trip_rank) * 12 + subset_index(compress(1 << pair_rank, trip_rank), 12, 1);
+        }
+    }
+
+    let straight_top = wheel.straight_top(all_ranks);
+    if straight_top != NO_STRAIGHT {
+        return STRAIGHT_START + (12 - straight_top as u16);
+    }
+
+    if trips != 0 {
+        let trip_rank = top_rank(trips);
+        let kickers = top_ranks(singles, 2);
+        return TRIPS_START + (12 - trip_rank) * 66 + subset_index(compress(kickers, trip_rank), 12, 2);
+    }
+
+    if pairs.count_ones() >= 2 {
+        let pair_ranks = top_ranks(pairs, 2);
+        let hi_rank = top_rank(pair_ranks);
+        let lo_rank = top_rank(pair_ranks & !(1 << hi_rank));
+        // A third pair can still play as the kicker.
+        let kicker = top_rank(all_ranks & !pair_ranks);
+        let kicker_index = subset_index(compress(compress(1 << kicker, hi_rank), lo_rank), 11, 1);
+        return TWO_PAIR_START + subset_index(pair_ranks, 13, 2) * 11 + kicker_index;
+    }
+
+    if pairs != 0 {
+        let pair_rank = top_rank(pairs);
+        let kickers = top_ranks(singles, 3);
+        return PAIR_START + (12 - pair_rank) * 220 + subset_index(compress(kickers, pair_rank), 12, 3);
+    }
+
+    HI_CARD_START + tables().five_rank_index[top_ranks(singles, 5) as usize]
+}
+
+fn evaluate_flush(suit_mask: u16, wheel: Wheel) -> u16 {
+    let straight_top = wheel.straight_top(suit_mask);
+    if straight_top != NO_STRAIGHT {
+        return STRAIGHT_FLUSH_START + (12 - straight_top as u16);
+    }
+    FLUSH_START + tables().five_rank_index[top_ranks(suit_mask, 5) as usize]
+}
+
+// The index of the highest rank in the mask.
+fn top_rank(mask: u16) -> u16 {
+    assert!(mask != 0);
+    15 - mask.leading_zeros() as u16
+}
+
+// The mask of just the highest num_ranks ranks in the given mask.
+fn top_ranks(mut mask: u16, num_ranks: u32) -> u16 {
+    while mask.count_ones() > num_ranks {
+        // Drop the lowest rank.
+        mask &= mask - 1;
+    }
+    mask
+}
+
+// Removes a rank which can't be in the mask, shifting the higher ranks down into its place.
+fn compress(mask: u16, removed_rank: u16) -> u16 {
+    let below = mask & ((1 << removed_rank) - 1);
+    let above = mask >> (removed_rank + 1);
+    below | (above << removed_rank)
+}
+
+/*
+How many masks with the same number of bits, out of the lowest num_ranks
+bits, are numerically greater than this one. Comparing masks numerically
+is the same as comparing their ranks highest first, like kickers are.
+
+Uses the combinatorial number system: the position of a combination in
+ascending order is the sum of C(position of the ith bit, i).
+*/
+fn subset_index(mask: u16, num_ranks: u16, num_chosen: u16) -> u16 {
+    let mut ascending_index = 0;
+    let mut bit_number = 1;
+    for position in 0..num_ranks {
+        if mask & (1 << position) != 0 {
+            ascending_index += choose(position, bit_number);
+            bit_number += 1;
+        }
+    }
+    choose(num_ranks, num_chosen) - 1 - ascending_index
+}
+
+fn choose(num_items: u16, num_chosen: u16) -> u16 {
+    if num_chosen > num_items {
+        return 0;
+    }
+    let mut combinations = 1;
+    for index in 0..num_chosen {
+        combinations = combinations * (num_items - index) / (index + 1);
+    }
+    combinations
+}
+
+// Which of the hand types (as numbered by Hand's Into<u8>) a rank belongs to.
+pub fn category(rank: u16) -> Result<u8, InvalidHandError> {
+    check_rank(rank)?;
+    Ok(standard_category(rank))
+}
+
+fn standard_category(rank: u16) -> u8 {
+    if rank < QUADS_START {
+        8
+    } else if rank < FULL_HOUSE_START {
+        7
+    } else if rank < FLUSH_START {
+        6
+    } else if rank < STRAIGHT_START {
+        5
+    } else if rank < TRIPS_START {
+        4
+    } else if rank < TWO_PAIR_START {
+        3
+    } else if rank < PAIR_START {
+        2
+    } else if rank < HI_CARD_START {
+        1
+    } else {
+        0
+    }
+}
+
+// Like category, for a rank from evaluate_with_rules.
+pub fn category_with_rules(rank: u16, rules: &Rules) -> Result<u8, InvalidHandError> {
+    check_rank(rank)?;
+    let mut start = 1;
+    for &category in rules.category_order().iter().rev() {
+        start += CATEGORY_SIZES[category as usize];
+        if rank < start {
+            return Ok(category);
+        }
+    }
+    Err(InvalidHandError::NotAHandRank(rank))
+}
+
+// The best rank of a category under the rules, where every category the rules rank higher comes first.
+fn rules_category_start(category: u8, rules: &Rules) -> u16 {
+    let better_categories = &rules.category_order()[(rules.category_strength(category) + 1)..];
+    1 + better_categories.iter().map(|&better| CATEGORY_SIZES[better as usize]).sum::<u16>()
+}
+
+// Turns a rank back into the hand it stands for.
+pub fn to_hand(rank: u16) -> Result<Hand, InvalidHandError> {
+    check_rank(rank)?;
+    Ok(all_hands()[rank as usize].unwrap())
+}
+
+// Every distinct hand, indexed by rank. Index 0 is unused.
+fn all_hands() -> &'static Vec<Option<Hand>> {
+    static HANDS: OnceLock<Vec<Option<Hand>>> = OnceLock::new();
+    HANDS.get_or_init(build_all_hands)
+}
+
+fn build_all_hands() -> Vec<Option<Hand>> {
+    let mut hands = vec![None; NUM_HAND_RANKS as usize + 1];
+    let mut ranks = Vec::with_capacity(5);
+    add_hands_with_ranks(&mut ranks, NUM_RANKS as u8, &mut hands);
+    for (rank, hand) in hands.iter().enumerate().skip(1) {
+        assert!(hand.is_some(), "No hand has rank {}", rank);
+    }
+    hands
+}
+
+// Picks five ranks in descending order, no more than four of each, and records the hands they make.
+fn add_hands_with_ranks(ranks: &mut Vec<Rank>, max_rank: u8, hands: &mut [Option<Hand>]) {
+    if ranks.len() == 5 {
+        add_hands_of(ranks, hands);
+        return;
+    }
+    for rank_index in (0..max_rank).rev() {
+        let rank = Rank::from(rank_index);
+        let num_copies = ranks.iter().filter(|&&other| other == rank).count();
+        if num_copies == 4 {
+            continue;
+        }
+        ranks.push(rank);
+        add_hands_with_ranks(ranks, rank_index + 1, hands);
+        ranks.pop();
+    }
+}
+
+fn add_hands_of(ranks: &[Rank], hands: &mut [Option<Hand>]) {
+    let mut cards = [card(Rank::Ace, Suit::Spades); 5]; // Dummies
+    let mut copies_so_far = [0u8; NUM_RANKS as usize];
+    for (index, &rank) in ranks.iter().enumerate() {
+        // Give copies of a rank different suits, and make sure not everything is one suit.
+        let copy = copies_so_far[rank as usize];
+        copies_so_far[rank as usize] += 1;
+        let suit = if index == 4 && copy == 0 { Suit::Hearts } else { Suit::from(copy) };
+        cards[index] = card(rank, suit);
+    }
+    record_hand(&cards, hands);
+
+    let all_different = copies_so_far.iter().all(|&copies| copies <= 1);
+    if all_different {
+        for flush_card in cards.iter_mut() {
+            flush_card.suit = Suit::Spades;
+        }
+        record_hand(&cards, hands);
+    }
+}
+
+fn record_hand(cards: &[Card; 5], hands: &mut [Option<Hand>]) {
+    let rank = evaluate_with_wheel(cards, Wheel::of(Rank::Five));
+    hands[rank as usize] = Some(Hand::get_hand(cards).unwrap());
+}
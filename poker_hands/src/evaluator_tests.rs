@@ -0,0 +1,74 @@
+#![cfg(test)]
+
+use rand::{Rng, SeedableRng, XorShiftRng};
+use super::*;
+use super::Hand::*;
+use super::evaluator::*;
+use test_helpers::assert_equal;
+
+use cards::Card;
+use cards::Rank::*;
+use cards::Suit::*;
+use cards::card;
+
+#[test]
+fn extreme_ranks() {
+    let royal_flush = [card(Ace, Hearts), card(King, Hearts), card(Queen, Hearts), card(Jack, Hearts), card(Ten, Hearts)];
+    assert_eq!(1, evaluate(&royal_flush).unwrap());
+    let quad_aces = [card(Ace, Hearts), card(Ace, Spades), card(Ace, Clubs), card(Ace, Diamonds), card(King, Hearts)];
+    assert_eq!(11, evaluate(&quad_aces).unwrap());
+    let seven_high = [card(Two, Hearts), card(Seven, Spades), card(Four, Clubs), card(Five, Diamonds), card(Three, Hearts)];
+    assert_eq!(NUM_HAND_RANKS, evaluate(&seven_high).unwrap());
+}
+
+#[test]
+fn ranks_are_ordered_like_hands() {
+    for rank in 1..NUM_HAND_RANKS {
+        let better = to_hand(rank).unwrap();
+        let worse = to_hand(rank + 1).unwrap();
+        assert!(better > worse, "{:?} (rank {}) should beat {:?}", better, rank, worse);
+    }
+}
+
+#[test]
+fn categories_match_hands() {
+    for rank in 1..(NUM_HAND_RANKS + 1) {
+        let hand_index: u8 = to_hand(rank).unwrap().into();
+        assert_eq!(hand_index, category(rank).unwrap());
+    }
+}
+
+#[test]
+fn finds_hands_in_seven_cards() {
+    let cards = [card(Two, Hearts), card(Ace, Spades), card(Five, Clubs), card(King, Hearts), card(Four, Diamonds), card(Three, Spades), card(Ace, Hearts)];
+    assert_equal(&Straight(StraightStr{hi_rank: Five}), &to_hand(evaluate(&cards).unwrap()).unwrap());
+
+    let cards = [card(Nine, Clubs), card(Nine, Spades), card(Five, Clubs), card(Five, Hearts), card(Four, Diamonds), card(Four, Spades), card(Ace, Hearts)];
+    assert_equal(&TwoPair(TwoPairStr{hi_rank: Nine, lo_rank: Five, kicker: Ace}), &to_hand(evaluate(&cards).unwrap()).unwrap());
+
+    let cards = [card(Nine, Clubs), card(Nine, Spades), card(Nine, Hearts), card(Five, Clubs), card(Five, Hearts), card(Five, Spades), card(Ace, Hearts)];
+    assert_equal(&FullHouse(FullHouseStr{three_of: Nine, two_of: Five}), &to_hand(evaluate(&cards).unwrap()).unwrap());
+}
+
+#[test]
+fn agrees_with_best_hand_of() {
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let mut deck: Vec<Card> = (0..52).map(|index| card(Rank::from(index / 4), Suit::from(index % 4))).collect();
+    for num_cards in 5..8 {
+        for _ in 0..5000 {
+            rng.shuffle(&mut deck);
+            let cards = &deck[..num_cards];
+            assert_equal(&Hand::best_hand_of(cards).unwrap(), &to_hand(evaluate(cards).unwrap()).unwrap());
+        }
+    }
+}
+
+#[test]
+fn bad_input_is_an_error() {
+    let two_cards = [card(Ace, Hearts), card(King, Hearts)];
+    assert_eq!(Err(InvalidHandError::TooFewCards{needed: 5, found: 2}), evaluate(&two_cards));
+    let five_aces = [card(Ace, Hearts), card(Ace, Spades), card(Ace, Clubs), card(Ace, Diamonds), card(Ace, Hearts)];
+    assert_eq!(Err(InvalidHandError::DuplicateCard(card(Ace, Hearts))), evaluate(&five_aces));
+    assert_eq!(Err(InvalidHandError::NotAHandRank(0)), category(0));
+    assert_eq!(Err(InvalidHandError::NotAHandRank(NUM_HAND_RANKS + 1)), to_hand(NUM_HAND_RANKS + 1));
+}
@@ -1,20 +1,16 @@
 #![cfg(test)]
 
-use std::fmt::Debug;
 use super::*;
 use super::Hand::*;
+use test_helpers::assert_equal;
 
 use cards::Card;
 use cards::Rank::*;
 use cards::Suit::*;
 use cards::card;
 
-fn assert_equal<T: Eq + Debug>(expected: &T, actual: &T) {
-    assert!(expected == actual, "Expected {:?} but got {:?}", expected, actual)
-}
-
 fn assert_makes_hand(cards: [Card; 5], hand: Hand) {
-    let actual_hand = Hand::get_hand(&cards);
+    let actual_hand = Hand::get_hand(cards).unwrap();
     assert_equal(&hand, &actual_hand);
 }
 
@@ -1,12 +1,33 @@
 extern crate cards;
+#[cfg(test)]
+extern crate rand;
 
+// The tests predate field init shorthand, and comparing a hand to itself is the point.
+#[allow(clippy::redundant_field_names, clippy::eq_op)]
 mod hand_order_tests;
 mod hand_making_tests;
 mod best_hand_tests;
-
-use cards::{Rank, Suit, Card};
+mod evaluator_tests;
+mod omaha_tests;
+mod low_tests;
+mod lowball_tests;
+mod rules_tests;
+mod test_helpers;
+mod error;
+
+pub mod evaluator;
+pub mod omaha;
+pub mod low;
+pub mod lowball;
+pub mod rules;
+
+pub use error::{InvalidHandError, InvalidRulesError};
+pub use rules::Rules;
+
+use cards::{Rank, Suit, Card, CardSet};
+use std::borrow::Borrow;
 use std::fmt::{Debug, Formatter};
-use std::cmp::{Eq, Ordering};
+use std::cmp::{Eq, Ordering, Reverse};
 
 // Rank arrays are used for kickers. They should be sorted descending.
 
@@ -43,12 +64,31 @@ pub enum Hand {
     StraightFlush(StraightFlushStr)
 }
 
+// A hand along with the five cards that make it.
+#[derive(Copy, Clone, Debug)]
+pub struct BestHand {
+    pub hand: Hand,
+    // Sorted by rank, highest first.
+    pub cards: [Card; 5],
+}
+
+impl BestHand {
+    pub fn uses(&self, card: Card) -> bool {
+        self.cards.contains(&card)
+    }
+
+    // How many of the given cards, like a player's hole cards, are part of the hand.
+    pub fn num_used_from(&self, cards: &[Card]) -> usize {
+        cards.iter().filter(|&&card| self.uses(card)).count()
+    }
+}
+
 use Hand::{HiCard, Pair, TwoPair, Trips, Straight, Flush, FullHouse, Quads, StraightFlush};
 
 // Macro facilitating returning as soon as a function returns a match.
 macro_rules! try_getting_hand(
-    ($function:path, $hand_type:path, $cards:ident) => {
-        match $function($cards) {
+    ($function:path, $hand_type:path, $($args:ident),+) => {
+        match $function($($args),+) {
             Some(hand) => return $hand_type(hand),
             None => {},
         };
@@ -71,14 +111,14 @@ impl<'a> AllFiveCardSubsets<'a> {
         }
 
         let current_indices = AllFiveCardSubsets::init_indices(cards.len());
-        AllFiveCardSubsets{all_cards: cards, current_indices: current_indices}
+        AllFiveCardSubsets{all_cards: cards, current_indices}
     }
 
     fn init_indices(total_cards: usize) -> Vec<bool> {
         // Start by using the first 5 cards.
-        let mut indices = vec![true; total_cards];
-        for index in 5..total_cards {
-            indices[index] = false;
+        let mut indices = vec![false; total_cards];
+        for index in indices.iter_mut().take(5) {
+            *index = true;
         }
         indices
     }
@@ -176,35 +216,37 @@ impl<'a> Iterator for AllFiveCardSubsets<'a> {
 }
 
 impl Hand {
-    // Pick out the best five-card hand.
-    pub fn best_hand_of(cards: &[Card]) -> Hand {
-        assert!(cards.len() >= 5);
-
-        // Starting value - worst hand ever.
-        let mut best_hand = HiCard(HiCardStr{ranks: [Rank::Seven, Rank::Five, Rank::Four, Rank::Three, Rank::Two]});
-        for five_cards in AllFiveCardSubsets::create(cards) {
-            let this_hand = Hand::get_hand(&five_cards);
-            if this_hand > best_hand {
-                best_hand = this_hand;
-            }
-        }
+    // Pick out the best five-card hand. The cards can be in any order.
+    pub fn best_hand_of<I>(cards: I) -> Result<Hand, InvalidHandError> where I: IntoIterator, I::Item: Borrow<Card> {
+        Hand::best_hand_with_cards(cards).map(|best_hand| best_hand.hand)
+    }
 
-        best_hand
+    /*
+    Picks out the best five-card hand, along with the cards that make it.
+    When several sets of five cards make equally good hands, which one is
+    returned is unspecified.
+    */
+    pub fn best_hand_with_cards<I>(cards: I) -> Result<BestHand, InvalidHandError> where I: IntoIterator, I::Item: Borrow<Card> {
+        Rules::standard().best_hand_with_cards(cards)
     }
 
+    // Makes five cards, in any order, into a hand.
+    pub fn get_hand<I>(cards: I) -> Result<Hand, InvalidHandError> where I: IntoIterator, I::Item: Borrow<Card> {
+        Rules::standard().get_hand(cards)
+    }
 
-    // Makes five cards into a hand.
-    pub fn get_hand(cards: &[Card]) -> Hand {
-        assert!(cards.len() == 5);
-        for i in 0..4 {
-            assert!(cards[i].rank >= cards[i + 1].rank);
-        }
+    // Makes five different cards, already sorted by rank, into a hand.
+    fn make_hand(cards: &[Card]) -> Hand {
+        Hand::make_hand_with_wheel(cards, Rank::Five)
+    }
 
-        try_getting_hand!(hand_builder::get_straight_flush, StraightFlush, cards);
+    // Like make_hand, but with the ace playing low in a straight up to wheel_top.
+    fn make_hand_with_wheel(cards: &[Card], wheel_top: Rank) -> Hand {
+        try_getting_hand!(hand_builder::get_straight_flush, StraightFlush, cards, wheel_top);
         try_getting_hand!(hand_builder::get_quads, Quads, cards);
         try_getting_hand!(hand_builder::get_full_house, FullHouse, cards);
         try_getting_hand!(hand_builder::get_flush, Flush, cards);
-        try_getting_hand!(hand_builder::get_straight, Straight, cards);
+        try_getting_hand!(hand_builder::get_straight, Straight, cards, wheel_top);
         try_getting_hand!(hand_builder::get_trips, Trips, cards);
         try_getting_hand!(hand_builder::get_two_pair, TwoPair, cards);
         try_getting_hand!(hand_builder::get_pair, Pair, cards);
@@ -227,6 +269,23 @@ impl Hand {
     }
 }
 
+// Sorts the cards by rank, highest first, which is how the hand builders expect them. Makes sure none of them are repeated.
+fn sorted_cards(mut cards: Vec<Card>) -> Result<Vec<Card>, InvalidHandError> {
+    check_for_duplicates(&cards)?;
+    cards.sort_by_key(|card| Reverse(card.rank));
+    Ok(cards)
+}
+
+fn check_for_duplicates<'a, I: IntoIterator<Item=&'a Card>>(cards: I) -> Result<(), InvalidHandError> {
+    let mut seen_cards = CardSet::empty();
+    for &card in cards {
+        if !seen_cards.insert(card) {
+            return Err(InvalidHandError::DuplicateCard(card));
+        }
+    }
+    Ok(())
+}
+
 mod hand_builder {
     use cards::{Rank, Suit, Card};
     use {HiCardStr, PairStr, TwoPairStr, TripsStr, StraightStr, FlushStr, FullHouseStr, QuadsStr, StraightFlushStr};
@@ -244,28 +303,20 @@ mod hand_builder {
     already sorted by rank.
     */
 
-    pub fn get_straight_flush(cards: &[Card]) -> Option<StraightFlushStr> {
-        let flush_suit = get_flush_suit(cards);
-        if !flush_suit.is_some() {
-            return None;
-        }
+    pub fn get_straight_flush(cards: &[Card], wheel_top: Rank) -> Option<StraightFlushStr> {
+        // No suit, no flush!
+        get_flush_suit(cards)?;
         // Found a flush; now look for a straight, too.
 
-        let straight_candidate = get_straight(cards);
-        match straight_candidate {
-            Some(StraightStr{hi_rank}) => Some(StraightFlushStr{hi_rank: hi_rank}),
-            None => None
-        }
+        let straight_candidate = get_straight(cards, wheel_top);
+        straight_candidate.map(|StraightStr{hi_rank}| StraightFlushStr{hi_rank})
     }
 
     pub fn get_flush(cards: &[Card]) -> Option<FlushStr> {
-        let flush_suit = get_flush_suit(cards);
-        if !flush_suit.is_some() {
-            return None; // No suit, no flush!
-        }
+        get_flush_suit(cards)?; // No suit, no flush!
 
         let HiCardStr{ranks} = get_hi_card(cards);
-        Some(FlushStr{ranks: ranks})
+        Some(FlushStr{ranks})
     }
 
     pub fn get_quads(cards: &[Card]) -> Option<QuadsStr> {
@@ -284,12 +335,12 @@ mod hand_builder {
         } else {
             (cards[0].rank, cards[1].rank, 1)
         };
-        for i in quad_start_index..(quad_start_index + 4) {
-            if cards[i].rank != quad_rank {
+        for card in &cards[quad_start_index..(quad_start_index + 4)] {
+            if card.rank != quad_rank {
                 return None
             }
         }
-        Some(QuadsStr{rank: quad_rank, kicker: kicker})
+        Some(QuadsStr{rank: quad_rank, kicker})
     }
 
     pub fn get_full_house(cards: &[Card]) -> Option<FullHouseStr> {
@@ -323,16 +374,17 @@ mod hand_builder {
         } else {
             (low_pair_rank, high_pair_rank)
         };
-        Some(FullHouseStr{three_of: three_of, two_of: two_of})
+        Some(FullHouseStr{three_of, two_of})
     }
 
-    pub fn get_straight(cards: &[Card]) -> Option<StraightStr> {
+    // The ace can play low in a straight up to wheel_top, like A-5-4-3-2.
+    pub fn get_straight(cards: &[Card], wheel_top: Rank) -> Option<StraightStr> {
         let mut wheel = false;
         for i in 1..5 {
             let this_card = cards[i];
             let prev_card = cards[i - 1];
             // Wheel detection
-            if i == 1 && prev_card.rank == Rank::Ace && this_card.rank == Rank::Five {
+            if i == 1 && prev_card.rank == Rank::Ace && this_card.rank == wheel_top {
                 wheel = true;
                 continue;
             }
@@ -344,7 +396,7 @@ mod hand_builder {
         }
         // Straight detected! Now make sure to get the wheel right.
         if wheel {
-            Some(StraightStr{hi_rank: Rank::Five})
+            Some(StraightStr{hi_rank: wheel_top})
         } else {
             Some(StraightStr{hi_rank: cards[0].rank})
         }
@@ -373,9 +425,9 @@ mod hand_builder {
             let this_rank = cards[i].rank;
             let prev_rank = cards[i - 1].rank;
             if this_rank == prev_rank {
-                if !high_pair_rank.is_some() {
+                if high_pair_rank.is_none() {
                     high_pair_rank = Some(this_rank);
-                } else if !low_pair_rank.is_some() {
+                } else if low_pair_rank.is_none() {
                     low_pair_rank = Some(this_rank);
                 } else {
                     panic!("Two pairs have already been found, yet have found a third one. High pair: {:?} Low pair: {:?} Third pair: {:?} Hand: {:?}",
@@ -395,15 +447,15 @@ mod hand_builder {
         let lo_rank = low_pair_rank.unwrap();
 
         let mut kicker = cards[0].rank;
-        for i in 1..5 {
-            let rank = cards[i].rank;
+        for card in &cards[1..5] {
+            let rank = card.rank;
             if rank != hi_rank && rank != lo_rank {
                 kicker = rank;
                 break;
             }
         }
 
-        Some(TwoPairStr{hi_rank: hi_rank, lo_rank: lo_rank, kicker: kicker})
+        Some(TwoPairStr{hi_rank, lo_rank, kicker})
     }
 
     pub fn get_pair(cards: &[Card]) -> Option<PairStr> {
@@ -416,15 +468,11 @@ mod hand_builder {
                 break;
             }
         }
-        if !pair_start.is_some() {
-            return None
-        }
-
-        let pair_rank = cards[pair_start.unwrap()].rank;
+        let pair_rank = cards[pair_start?].rank;
         let mut kickers = [Rank::Ace, Rank::Ace, Rank::Ace]; // Dummy values.
         let mut kicker_index = 0;
-        for i in 0..5 {
-            let this_rank = cards[i].rank;
+        for card in &cards[0..5] {
+            let this_rank = card.rank;
             if this_rank == pair_rank {
                 continue;
             }
@@ -433,7 +481,7 @@ mod hand_builder {
         }
         assert!(kicker_index == 3);
 
-        return Some(PairStr{rank: pair_rank, kickers: kickers})
+        Some(PairStr{rank: pair_rank, kickers})
     }
 
 /* Not optional because this assumes nothing better than high card
@@ -460,9 +508,9 @@ mod hand_builder {
     }
 }
 
-impl Into<u8> for Hand {
-    fn into(self) -> u8 {
-        match self {
+impl From<Hand> for u8 {
+    fn from(hand: Hand) -> Self {
+        match hand {
             HiCard(..) => 0,
             Pair(..) => 1,
             TwoPair(..) => 2,
@@ -0,0 +1,117 @@
+/*
+Eight-or-better lows, for the low half of a split pot. A low is five cards
+of different ranks, all eight or lower, with aces counting as low and
+straights and flushes not counting against it. Lows compare by their
+highest card first, so 7-5-4-3-2 beats 8-4-3-2-A.
+*/
+
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
+
+use cards::{Card, Rank};
+
+// The ranks which can make a low, lowest first.
+const LOW_RANKS: [Rank; 8] = [Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Five, Rank::Six, Rank::Seven, Rank::Eight];
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct LowHand {
+    // One bit for each rank in the hand, numbered like LOW_RANKS.
+    mask: u8,
+}
+
+impl LowHand {
+    // The ranks in the hand, highest first.
+    pub fn ranks(&self) -> [Rank; 5] {
+        let mut ranks = [Rank::Ace; 5]; // Dummies
+        let mut num_found = 0;
+        for low_index in (0..LOW_RANKS.len()).rev() {
+            if self.mask & (1 << low_index) != 0 {
+                ranks[num_found] = LOW_RANKS[low_index];
+                num_found += 1;
+            }
+        }
+        assert!(num_found == 5);
+        ranks
+    }
+}
+
+// Better lows are greater, the same as with Hand.
+impl Ord for LowHand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Comparing the masks as numbers compares the highest ranks first.
+        other.mask.cmp(&self.mask)
+    }
+}
+
+impl PartialOrd for LowHand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for LowHand {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let ranks: Vec<String> = self.ranks().iter().map(|rank| rank.to_string()).collect();
+        write!(f, "{}", ranks.join("-"))
+    }
+}
+
+impl Debug for LowHand {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{} low", self)
+    }
+}
+
+// The best low out of any five of the cards, if there is one.
+pub fn best_low_of(cards: &[Card]) -> Option<LowHand> {
+    lowest_ranks(low_rank_mask(cards), 5).map(|mask| LowHand{mask})
+}
+
+// The best low made from exactly two hole cards and three board cards, like in Omaha.
+pub fn best_omaha_low(hole_cards: &[Card], board: &[Card]) -> Option<LowHand> {
+    let board_mask = low_rank_mask(board);
+    let mut best_mask = None;
+    for (index, &first_card) in hole_cards.iter().enumerate() {
+        for &second_card in &hole_cards[(index + 1)..] {
+            let hole_mask = low_rank_mask(&[first_card, second_card]);
+            if hole_mask.count_ones() != 2 {
+                // One of them is too high, or they're a pair.
+                continue;
+            }
+            // With the hole cards fixed, the lowest three other board ranks make the best low.
+            if let Some(board_part) = lowest_ranks(board_mask & !hole_mask, 3) {
+                let mask = hole_mask | board_part;
+                if best_mask.is_none_or(|best| mask < best) {
+                    best_mask = Some(mask);
+                }
+            }
+        }
+    }
+    best_mask.map(|mask| LowHand{mask})
+}
+
+fn low_rank_mask(cards: &[Card]) -> u8 {
+    let mut mask = 0;
+    for card in cards {
+        if let Some(low_index) = LOW_RANKS.iter().position(|&rank| rank == card.rank) {
+            mask |= 1 << low_index;
+        }
+    }
+    mask
+}
+
+// Just the lowest num_ranks ranks of the mask, if it has that many.
+fn lowest_ranks(mask: u8, num_ranks: u32) -> Option<u8> {
+    if mask.count_ones() < num_ranks {
+        return None;
+    }
+    let mut remaining = mask;
+    let mut lowest = 0;
+    for _ in 0..num_ranks {
+        // Move the lowest set bit over.
+        let lowest_bit = remaining & remaining.wrapping_neg();
+        lowest |= lowest_bit;
+        remaining &= !lowest_bit;
+    }
+    Some(lowest)
+}
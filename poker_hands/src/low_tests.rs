@@ -0,0 +1,49 @@
+#![cfg(test)]
+
+use super::low::*;
+use test_helpers::cards;
+
+use cards::Rank::*;
+
+fn low_of(cards_string: &str) -> Option<LowHand> {
+    best_low_of(&cards(cards_string))
+}
+
+fn omaha_low_of(hole_string: &str, board_string: &str) -> Option<LowHand> {
+    best_omaha_low(&cards(hole_string), &cards(board_string))
+}
+
+#[test]
+fn lows_need_five_low_ranks() {
+    assert_eq!([Five, Four, Three, Two, Ace], low_of("As2d3h4c5s").unwrap().ranks());
+    // Pairs and high cards are skipped.
+    assert_eq!([Eight, Seven, Six, Three, Ace], low_of("AsAd8h7c6sKd3h").unwrap().ranks());
+    assert_eq!(None, low_of("As2d3h4c9s"));
+    assert_eq!(None, low_of("As2d3h4c4sKdQd"));
+    assert_eq!("8-7-6-3-A", low_of("Ad8h7c6s3h").unwrap().to_string());
+}
+
+#[test]
+fn lows_compare_highest_card_first() {
+    let wheel = low_of("As2d3h4c5s").unwrap();
+    let six_low = low_of("As2d3h4c6s").unwrap();
+    let seven_five = low_of("7s5d4h3c2s").unwrap();
+    let eight_low = low_of("8s4d3h2cAs").unwrap();
+    assert!(wheel > six_low);
+    assert!(six_low > seven_five);
+    assert!(seven_five > eight_low);
+    assert_eq!(wheel, low_of("Ah2h3h4h5hKs").unwrap());
+}
+
+#[test]
+fn omaha_lows_use_two_hole_cards() {
+    assert_eq!([Six, Four, Three, Two, Ace], omaha_low_of("As2dKhKc", "3h4c6sQdJd").unwrap().ranks());
+    // The best low might not use the lowest hole cards.
+    assert_eq!([Five, Four, Three, Two, Ace], omaha_low_of("As2d3h9c", "4c5s3dQdJd").unwrap().ranks());
+    // Only one low card in the hole.
+    assert_eq!(None, omaha_low_of("AsKdKhQc", "2h3c4s5d6d"));
+    // Only two low cards on the board.
+    assert_eq!(None, omaha_low_of("As2d3h4c", "5h6cKsQdJd"));
+    // A paired hole card counts once.
+    assert_eq!(None, omaha_low_of("AsAd9h9c", "2h3cKsQdJd"));
+}
@@ -0,0 +1,221 @@
+/*
+Lowball hands, where the lowest hand wins. Like Hand, better hands compare
+as greater.
+
+In ace-to-five (as in razz), aces are low and straights and flushes don't
+count, so the best hand is 5-4-3-2-A. Pairs and the like still count
+against a hand.
+
+In deuce-to-seven, aces are high and straights and flushes count, so hands
+rank exactly the opposite of high hands, except that A-5-4-3-2 isn't a
+straight. The best hand is 7-5-4-3-2, known as "number one".
+*/
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+
+use cards::{Card, Rank};
+use {AllFiveCardSubsets, Hand, HiCardStr, FlushStr, InvalidHandError, sorted_cards};
+use Hand::{HiCard, Pair, Straight, Flush, StraightFlush};
+
+#[derive(Copy, Clone)]
+pub struct AceToFiveLow {
+    // Grouped like in high hands: bigger groups of the same rank first, and within those, higher ranks first, counting aces as low.
+    pub ranks: [Rank; 5],
+}
+
+#[derive(Copy, Clone)]
+pub struct DeuceToSevenLow {
+    // The hand as a high hand would be, except with A-5-4-3-2 as ace high instead of a straight.
+    pub hand: Hand,
+}
+
+impl AceToFiveLow {
+    // Like Hand::get_hand, with aces low and no straights or flushes.
+    pub fn get_hand<I>(cards: I) -> Result<AceToFiveLow, InvalidHandError> where I: IntoIterator, I::Item: Borrow<Card> {
+        let cards = five_sorted_cards(cards)?;
+        Ok(AceToFiveLow::make_hand(&cards))
+    }
+
+    // Like Hand::best_hand_of, so a paired card is only played when there aren't five different ranks.
+    pub fn best_hand_of<I>(cards: I) -> Result<AceToFiveLow, InvalidHandError> where I: IntoIterator, I::Item: Borrow<Card> {
+        let cards = enough_sorted_cards(cards)?;
+        Ok(AllFiveCardSubsets::create(&cards).map(|five_cards| AceToFiveLow::make_hand(&five_cards)).max().unwrap())
+    }
+
+    fn make_hand(cards: &[Card]) -> AceToFiveLow {
+        let mut counts = [0u8; 13];
+        for card in cards {
+            counts[ace_low_index(card.rank) as usize] += 1;
+        }
+        let mut ranks: Vec<Rank> = cards.iter().map(|card| card.rank).collect();
+        ranks.sort_by(|&first, &second| {
+            let first_key = (counts[ace_low_index(first) as usize], ace_low_index(first));
+            let second_key = (counts[ace_low_index(second) as usize], ace_low_index(second));
+            second_key.cmp(&first_key)
+        });
+        AceToFiveLow{ranks: [ranks[0], ranks[1], ranks[2], ranks[3], ranks[4]]}
+    }
+
+    // How many cards are in each group of the same rank, biggest first.
+    fn group_sizes(&self) -> Vec<u8> {
+        let mut sizes = Vec::with_capacity(5);
+        let mut index = 0;
+        while index < 5 {
+            let size = self.ranks[index..].iter().take_while(|&&rank| rank == self.ranks[index]).count();
+            sizes.push(size as u8);
+            index += size;
+        }
+        sizes
+    }
+
+    // Like "seven-five low" or "pair of nines".
+    pub fn name(&self) -> String {
+        let sizes = self.group_sizes();
+        match sizes[0] {
+            1 => format!("{}-{} low", rank_name(self.ranks[0]), rank_name(self.ranks[1])),
+            2 if sizes[1] == 2 => format!("two pair, {} and {}", plural_rank_name(self.ranks[0]), plural_rank_name(self.ranks[2])),
+            2 => format!("pair of {}", plural_rank_name(self.ranks[0])),
+            3 if sizes[1] == 2 => format!("full house, {} full of {}", plural_rank_name(self.ranks[0]), plural_rank_name(self.ranks[3])),
+            3 => format!("trip {}", plural_rank_name(self.ranks[0])),
+            _ => format!("quad {}", plural_rank_name(self.ranks[0])),
+        }
+    }
+}
+
+impl Ord for AceToFiveLow {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Fewer and smaller groups are better, then lower ranks, so compare the other way around.
+        let these_ranks: Vec<u8> = self.ranks.iter().map(|&rank| ace_low_index(rank)).collect();
+        let other_ranks: Vec<u8> = other.ranks.iter().map(|&rank| ace_low_index(rank)).collect();
+        (other.group_sizes(), other_ranks).cmp(&(self.group_sizes(), these_ranks))
+    }
+}
+
+impl PartialOrd for AceToFiveLow {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Eq for AceToFiveLow {}
+impl PartialEq for AceToFiveLow {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Debug for AceToFiveLow {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{} ({:?})", self.name(), self.ranks)
+    }
+}
+
+impl DeuceToSevenLow {
+    // Like Hand::get_hand, except that A-5-4-3-2 is ace high.
+    pub fn get_hand<I>(cards: I) -> Result<DeuceToSevenLow, InvalidHandError> where I: IntoIterator, I::Item: Borrow<Card> {
+        let cards = five_sorted_cards(cards)?;
+        Ok(DeuceToSevenLow::make_hand(&cards))
+    }
+
+    // Like Hand::best_hand_of, so pairs, straights and flushes are dodged when the other cards allow it.
+    pub fn best_hand_of<I>(cards: I) -> Result<DeuceToSevenLow, InvalidHandError> where I: IntoIterator, I::Item: Borrow<Card> {
+        let cards = enough_sorted_cards(cards)?;
+        Ok(AllFiveCardSubsets::create(&cards).map(|five_cards| DeuceToSevenLow::make_hand(&five_cards)).max().unwrap())
+    }
+
+    fn make_hand(cards: &[Card]) -> DeuceToSevenLow {
+        let wheel_ranks = [Rank::Ace, Rank::Five, Rank::Four, Rank::Three, Rank::Two];
+        let hand = match Hand::make_hand(cards) {
+            Straight(straight) if straight.hi_rank == Rank::Five => HiCard(HiCardStr{ranks: wheel_ranks}),
+            StraightFlush(straight) if straight.hi_rank == Rank::Five => Flush(FlushStr{ranks: wheel_ranks}),
+            hand => hand,
+        };
+        DeuceToSevenLow{hand}
+    }
+
+    // Like "number one", "eight-six low" or "pair of nines".
+    pub fn name(&self) -> String {
+        match self.hand {
+            HiCard(HiCardStr{ranks}) => {
+                let seven_lows = [
+                    [Rank::Seven, Rank::Five, Rank::Four, Rank::Three, Rank::Two],
+                    [Rank::Seven, Rank::Six, Rank::Four, Rank::Three, Rank::Two],
+                    [Rank::Seven, Rank::Six, Rank::Five, Rank::Three, Rank::Two],
+                    [Rank::Seven, Rank::Six, Rank::Five, Rank::Four, Rank::Two],
+                ];
+                let numbers = ["one", "two", "three", "four"];
+                match seven_lows.iter().position(|seven_low| *seven_low == ranks) {
+                    Some(index) => format!("number {}", numbers[index]),
+                    None => format!("{}-{} low", rank_name(ranks[0]), rank_name(ranks[1])),
+                }
+            },
+            Pair(pair) => format!("pair of {}", plural_rank_name(pair.rank)),
+            hand => Hand::name_hand_index(u8::from(hand) as usize).to_lowercase(),
+        }
+    }
+}
+
+impl Ord for DeuceToSevenLow {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.hand.cmp(&self.hand)
+    }
+}
+
+impl PartialOrd for DeuceToSevenLow {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Eq for DeuceToSevenLow {}
+impl PartialEq for DeuceToSevenLow {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Debug for DeuceToSevenLow {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{} ({:?})", self.name(), self.hand)
+    }
+}
+
+fn five_sorted_cards<I>(cards: I) -> Result<Vec<Card>, InvalidHandError> where I: IntoIterator, I::Item: Borrow<Card> {
+    let cards: Vec<Card> = cards.into_iter().map(|card| *card.borrow()).collect();
+    if cards.len() != 5 {
+        return Err(InvalidHandError::WrongNumberOfCards{expected: 5, found: cards.len()});
+    }
+    sorted_cards(cards)
+}
+
+fn enough_sorted_cards<I>(cards: I) -> Result<Vec<Card>, InvalidHandError> where I: IntoIterator, I::Item: Borrow<Card> {
+    let cards: Vec<Card> = cards.into_iter().map(|card| *card.borrow()).collect();
+    if cards.len() < 5 {
+        return Err(InvalidHandError::TooFewCards{needed: 5, found: cards.len()});
+    }
+    sorted_cards(cards)
+}
+
+// Numbers ranks from 0 for an ace up to 12 for a king.
+fn ace_low_index(rank: Rank) -> u8 {
+    match rank {
+        Rank::Ace => 0,
+        rank => rank as u8 + 1,
+    }
+}
+
+fn rank_name(rank: Rank) -> String {
+    match rank {
+        Rank::Two => "deuce".to_string(),
+        rank => format!("{:?}", rank).to_lowercase(),
+    }
+}
+
+fn plural_rank_name(rank: Rank) -> String {
+    match rank {
+        Rank::Six => "sixes".to_string(),
+        rank => rank_name(rank) + "s",
+    }
+}
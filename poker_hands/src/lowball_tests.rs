@@ -0,0 +1,80 @@
+#![cfg(test)]
+
+use super::lowball::*;
+use super::InvalidHandError;
+use test_helpers::cards;
+
+use cards::Rank::*;
+
+fn ace_to_five(cards_string: &str) -> AceToFiveLow {
+    AceToFiveLow::best_hand_of(cards(cards_string)).unwrap()
+}
+
+fn deuce_to_seven(cards_string: &str) -> DeuceToSevenLow {
+    DeuceToSevenLow::best_hand_of(cards(cards_string)).unwrap()
+}
+
+#[test]
+fn ace_to_five_ignores_straights_and_flushes() {
+    let wheel = ace_to_five("5h4h3h2hAh");
+    assert_eq!([Five, Four, Three, Two, Ace], wheel.ranks);
+    assert_eq!("five-four low", wheel.name());
+    assert!(wheel > ace_to_five("6s4d3h2cAs"));
+    assert!(ace_to_five("7s5d4h3c2s") > ace_to_five("7s6d4h3c2s"));
+    assert!(ace_to_five("7s6d4h3c2s") > ace_to_five("8s4d3h2cAs"));
+    assert_eq!(ace_to_five("7s5d4h3c2s"), ace_to_five("7h5h4h3h2h"));
+}
+
+#[test]
+fn ace_to_five_pairs_are_bad() {
+    let pair = ace_to_five("KsKd4h3c2s");
+    assert_eq!([King, King, Four, Three, Two], pair.ranks);
+    assert_eq!("pair of kings", pair.name());
+    assert!(ace_to_five("KsQdJhTc9s") > pair);
+    assert!(ace_to_five("AsAd4h3c2s") > ace_to_five("2s2d4h3cAs"));
+    assert!(ace_to_five("2s2d3h3cAs") < pair);
+    assert!(ace_to_five("2s2d2h3cAs") < ace_to_five("2s2d3h3cAs"));
+    assert_eq!("trip deuces", ace_to_five("2s2d2h3cAs").name());
+}
+
+#[test]
+fn razz_picks_the_best_five_of_seven() {
+    let razz_hand = ace_to_five("KsKd7h6c4sAd2h");
+    assert_eq!([Seven, Six, Four, Two, Ace], razz_hand.ranks);
+    assert_eq!("seven-six low", razz_hand.name());
+    assert_eq!("two pair, jacks and fours", ace_to_five("QsQdJhJcQh4s4d").name());
+}
+
+#[test]
+fn deuce_to_seven_counts_straights_and_flushes() {
+    let number_one = deuce_to_seven("7s5d4h3c2s");
+    assert_eq!("number one", number_one.name());
+    assert_eq!("number two", deuce_to_seven("7s6d4h3c2s").name());
+    assert_eq!("eight-five low", deuce_to_seven("8s5d4h3c2s").name());
+    assert!(number_one > deuce_to_seven("7s6d4h3c2s"));
+    assert!(number_one > deuce_to_seven("7h5h4h3h2h"));
+    assert_eq!("flush", deuce_to_seven("7h5h4h3h2h").name());
+    assert!(deuce_to_seven("AsKdQhJc9s") > deuce_to_seven("6s5d4h3c2s"));
+    assert!(deuce_to_seven("AsKdQhJc9s") > deuce_to_seven("2s2d4h3c5s"));
+    assert_eq!("pair of deuces", deuce_to_seven("2s2d4h3c5s").name());
+}
+
+#[test]
+fn deuce_to_seven_aces_are_high() {
+    let ace_high = deuce_to_seven("As5d4h3c2s");
+    assert_eq!("ace-five low", ace_high.name());
+    assert!(ace_high > deuce_to_seven("6s5d4h3c2s"));
+    assert!(deuce_to_seven("KsQdJhTc8s") > ace_high);
+    assert!(deuce_to_seven("As5s4s3s2s") < ace_high);
+    assert_eq!("number one", deuce_to_seven("7s5d4h3c2sAsAd").name());
+}
+
+#[test]
+fn lowball_hands_need_valid_cards() {
+    assert_eq!(Err(InvalidHandError::WrongNumberOfCards{expected: 5, found: 4}),
+               AceToFiveLow::get_hand(cards("As2s3s4s")).map(|hand| hand.ranks));
+    assert_eq!(Err(InvalidHandError::TooFewCards{needed: 5, found: 4}),
+               DeuceToSevenLow::best_hand_of(cards("As2s3s4s")).map(|hand| hand.hand));
+    assert_eq!(Err(InvalidHandError::DuplicateCard(cards("As")[0])),
+               AceToFiveLow::best_hand_of(cards("As2s3s4sAs")).map(|hand| hand.ranks));
+}
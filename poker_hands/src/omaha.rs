@@ -0,0 +1,96 @@
+/*
+Omaha hands, which are made from exactly two of a player's hole cards and
+exactly three of the board cards. Players get four hole cards in regular
+Omaha, and five or six in the bigger variants.
+*/
+
+use std::cmp::Ordering;
+
+use cards::Card;
+use evaluator;
+use {BestHand, InvalidHandError, Rules, check_for_duplicates};
+
+pub const MIN_HOLE_CARDS: usize = 4;
+pub const MAX_HOLE_CARDS: usize = 6;
+// The board needs at least a flop to make a hand from.
+pub const MIN_BOARD_CARDS: usize = 3;
+pub const MAX_BOARD_CARDS: usize = 5;
+
+// Evaluates the best Omaha hand into a rank, numbered like evaluator::evaluate.
+pub fn evaluate(hole_cards: &[Card], board: &[Card]) -> Result<u16, InvalidHandError> {
+    evaluate_with_rules(hole_cards, board, &Rules::standard())
+}
+
+// Like evaluate, but under the given rules, numbered like evaluator::evaluate_with_rules.
+pub fn evaluate_with_rules(hole_cards: &[Card], board: &[Card], rules: &Rules) -> Result<u16, InvalidHandError> {
+    check_cards(hole_cards, board, rules)?;
+    let mut best_rank = evaluator::NUM_HAND_RANKS;
+    for_each_omaha_hand(hole_cards, board, |five_cards| {
+        let rank = evaluator::evaluate_checked_with_rules(five_cards, rules);
+        if rank < best_rank {
+            best_rank = rank;
+        }
+    });
+    Ok(best_rank)
+}
+
+fn check_cards(hole_cards: &[Card], board: &[Card], rules: &Rules) -> Result<(), InvalidHandError> {
+    if hole_cards.len() < MIN_HOLE_CARDS {
+        return Err(InvalidHandError::TooFewCards{needed: MIN_HOLE_CARDS, found: hole_cards.len()});
+    }
+    if hole_cards.len() > MAX_HOLE_CARDS {
+        return Err(InvalidHandError::TooManyCards{allowed: MAX_HOLE_CARDS, found: hole_cards.len()});
+    }
+    if board.len() < MIN_BOARD_CARDS {
+        return Err(InvalidHandError::TooFewCards{needed: MIN_BOARD_CARDS, found: board.len()});
+    }
+    if board.len() > MAX_BOARD_CARDS {
+        return Err(InvalidHandError::TooManyCards{allowed: MAX_BOARD_CARDS, found: board.len()});
+    }
+    check_for_duplicates(hole_cards.iter().chain(board))?;
+    rules.check_in_deck(hole_cards.iter().chain(board))
+}
+
+// Picks out the best Omaha hand, along with the two hole cards and three board cards that make it.
+pub fn best_hand(hole_cards: &[Card], board: &[Card]) -> Result<BestHand, InvalidHandError> {
+    best_hand_with_rules(hole_cards, board, &Rules::standard())
+}
+
+// Like best_hand, but under the given rules.
+pub fn best_hand_with_rules(hole_cards: &[Card], board: &[Card], rules: &Rules) -> Result<BestHand, InvalidHandError> {
+    check_cards(hole_cards, board, rules)?;
+
+    let mut best_hand: Option<BestHand> = None;
+    for_each_omaha_hand(hole_cards, board, |five_cards| {
+        let this_hand = rules.best_hand_with_cards(five_cards).unwrap();
+        let better = match best_hand {
+            Some(BestHand{hand, ..}) => rules.cmp_hands(&this_hand.hand, &hand) == Ordering::Greater,
+            None => true,
+        };
+        if better {
+            best_hand = Some(this_hand);
+        }
+    });
+    Ok(best_hand.unwrap())
+}
+
+// Calls back with every five cards made of two hole cards and three board cards.
+fn for_each_omaha_hand<F: FnMut(&[Card; 5])>(hole_cards: &[Card], board: &[Card], mut callback: F) {
+    let mut five_cards = [hole_cards[0]; 5]; // Dummies
+    for first_hole in 0..hole_cards.len() {
+        for second_hole in (first_hole + 1)..hole_cards.len() {
+            five_cards[0] = hole_cards[first_hole];
+            five_cards[1] = hole_cards[second_hole];
+            for first_board in 0..board.len() {
+                for second_board in (first_board + 1)..board.len() {
+                    for third_board in (second_board + 1)..board.len() {
+                        five_cards[2] = board[first_board];
+                        five_cards[3] = board[second_board];
+                        five_cards[4] = board[third_board];
+                        callback(&five_cards);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+#![cfg(test)]
+
+use rand::{Rng, SeedableRng, XorShiftRng};
+use super::*;
+use super::Hand::*;
+use super::omaha::*;
+use test_helpers::assert_equal;
+
+use cards::Card;
+use cards::Rank::*;
+use cards::Suit::*;
+use cards::card;
+
+#[test]
+fn two_hole_cards_and_three_board_cards() {
+    // Four spades on the board aren't a flush with only one spade in the hole.
+    let board = [card(King, Spades), card(Queen, Spades), card(Jack, Spades), card(Ten, Spades), card(Two, Diamonds)];
+    let hole_cards = [card(Ace, Spades), card(Nine, Hearts), card(Eight, Clubs), card(Seven, Diamonds)];
+    let made_hand = best_hand(&hole_cards, &board).unwrap();
+    assert_equal(&Straight(StraightStr{hi_rank: Queen}), &made_hand.hand);
+    assert_eq!(2, made_hand.num_used_from(&hole_cards));
+    assert_eq!(3, made_hand.num_used_from(&board));
+
+    // Quads in the hole are only a pair.
+    let board = [card(King, Hearts), card(Eight, Spades), card(Three, Clubs)];
+    let hole_cards = [card(Ace, Spades), card(Ace, Hearts), card(Ace, Clubs), card(Ace, Diamonds)];
+    assert_equal(&Pair(PairStr{rank: Ace, kickers: [King, Eight, Three]}), &best_hand(&hole_cards, &board).unwrap().hand);
+}
+
+#[test]
+fn bigger_variants() {
+    let board = [card(King, Spades), card(Queen, Spades), card(Two, Spades), card(Ten, Hearts), card(Two, Diamonds)];
+    let five_hole_cards = [card(Ace, Hearts), card(Jack, Hearts), card(Nine, Clubs), card(Eight, Clubs), card(Two, Hearts)];
+    assert_equal(&Straight(StraightStr{hi_rank: Ace}), &best_hand(&five_hole_cards, &board).unwrap().hand);
+
+    let mut six_hole_cards = five_hole_cards.to_vec();
+    six_hole_cards.push(card(King, Hearts));
+    let hand = best_hand(&six_hole_cards, &board).unwrap().hand;
+    assert_equal(&FullHouse(FullHouseStr{three_of: Two, two_of: King}), &hand);
+}
+
+#[test]
+fn agrees_with_best_hand() {
+    let mut rng = XorShiftRng::from_seed([4, 3, 2, 1]);
+    let mut deck: Vec<Card> = (0..52).map(Card::from).collect();
+    for num_hole_cards in MIN_HOLE_CARDS..(MAX_HOLE_CARDS + 1) {
+        for _ in 0..300 {
+            rng.shuffle(&mut deck);
+            let (hole_cards, rest) = deck.split_at(num_hole_cards);
+            let board = &rest[..5];
+            let made_hand = best_hand(hole_cards, board).unwrap();
+            assert_equal(&made_hand.hand, &evaluator::to_hand(evaluate(hole_cards, board).unwrap()).unwrap());
+        }
+    }
+}
+
+#[test]
+fn reject_invalid_cards() {
+    let board = [card(King, Spades), card(Queen, Spades), card(Two, Spades)];
+    let three_hole_cards = [card(Ace, Hearts), card(Jack, Hearts), card(Nine, Clubs)];
+    assert_equal(&Err(InvalidHandError::TooFewCards{needed: 4, found: 3}), &best_hand(&three_hole_cards, &board).map(|best| best.hand));
+
+    let hole_cards = [card(Ace, Hearts), card(Jack, Hearts), card(Nine, Clubs), card(King, Spades)];
+    assert_equal(&Err(InvalidHandError::DuplicateCard(card(King, Spades))), &best_hand(&hole_cards, &board).map(|best| best.hand));
+
+    assert_eq!(Err(InvalidHandError::TooFewCards{needed: 4, found: 3}), evaluate(&three_hole_cards, &board));
+    assert_eq!(Err(InvalidHandError::TooFewCards{needed: 3, found: 2}), evaluate(&hole_cards, &board[..2]));
+    assert_eq!(Err(InvalidHandError::DuplicateCard(card(King, Spades))), evaluate(&hole_cards, &board));
+}
+
+#[test]
+fn ranks_and_hands_reject_the_same_cards() {
+    let rules = Rules::short_deck();
+    let hole_cards = [card(Ace, Spades), card(King, Spades), card(Queen, Spades), card(Jack, Spades)];
+    let board = [card(Nine, Hearts), card(Two, Clubs), card(Seven, Diamonds)];
+    let two = card(Two, Clubs);
+    assert_eq!(Err(InvalidHandError::NotInDeck(two)), evaluate_with_rules(&hole_cards, &board, &rules));
+    assert_equal(&Err(InvalidHandError::NotInDeck(two)), &best_hand_with_rules(&hole_cards, &board, &rules).map(|best| best.hand));
+}
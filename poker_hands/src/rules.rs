@@ -0,0 +1,156 @@
+/*
+The rules that decide which cards are dealt and how hands rank against each
+other. Standard rules use all 52 cards, with the usual hand order and
+A-5-4-3-2 as the lowest straight.
+
+Short-deck (six-plus) hold'em takes out the twos through fives, which makes
+flushes rarer than full houses, so a flush ranks above a full house there.
+With no five, the ace plays low in A-6-7-8-9 instead.
+*/
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+
+use cards::{Card, CardSet, Rank};
+use {AllFiveCardSubsets, BestHand, Hand, InvalidHandError, InvalidRulesError, NUM_HANDS, cmp_same_type_hand, sorted_cards};
+
+// Made by create, which checks that the rules can be played.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Rules {
+    // The lowest rank in the deck. Every rank from this one up to the ace is in it, in all four suits.
+    lowest_rank: Rank,
+    // The hand categories, numbered like Hand's Into<u8>, from worst to best. Each one appears exactly once.
+    category_order: [u8; NUM_HANDS],
+    // The top card of the straight where the ace plays low, like the five in A-5-4-3-2. Always three ranks above the lowest one.
+    wheel_top: Rank,
+}
+
+// The ace-low straight needs the four lowest ranks to stay apart from the ace-high one.
+const HIGHEST_LOWEST_RANK: Rank = Rank::Nine;
+
+impl Rules {
+    /*
+    Rules dealing every rank from lowest_rank up, with the hand categories
+    (numbered like Hand's Into<u8>) ranked from worst to best as in
+    category_order. The ace plays low in a straight with the four lowest
+    ranks.
+    */
+    pub fn create(lowest_rank: Rank, category_order: [u8; NUM_HANDS]) -> Result<Rules, InvalidRulesError> {
+        let mut sorted_order = category_order;
+        sorted_order.sort();
+        if sorted_order.iter().enumerate().any(|(index, &category)| category as usize != index) {
+            return Err(InvalidRulesError::NotACategoryOrder(category_order));
+        }
+        if lowest_rank > HIGHEST_LOWEST_RANK {
+            return Err(InvalidRulesError::TooFewRanks(lowest_rank));
+        }
+        let wheel_top = Rank::from(lowest_rank as u8 + 3);
+        Ok(Rules{lowest_rank, category_order, wheel_top})
+    }
+
+    pub fn standard() -> Rules {
+        Rules::create(Rank::Two, [0, 1, 2, 3, 4, 5, 6, 7, 8]).unwrap()
+    }
+
+    // Six-plus hold'em.
+    pub fn short_deck() -> Rules {
+        Rules::create(Rank::Six, [0, 1, 2, 3, 4, 6, 5, 7, 8]).unwrap()
+    }
+
+    pub fn lowest_rank(&self) -> Rank {
+        self.lowest_rank
+    }
+
+    pub fn category_order(&self) -> &[u8; NUM_HANDS] {
+        &self.category_order
+    }
+
+    pub fn wheel_top(&self) -> Rank {
+        self.wheel_top
+    }
+
+    // Every card that gets dealt under these rules.
+    pub fn deck(&self) -> CardSet {
+        CardSet::full().iter().filter(|&card| self.in_deck(card)).collect()
+    }
+
+    pub fn deck_size(&self) -> usize {
+        self.deck().len()
+    }
+
+    pub fn in_deck(&self, card: Card) -> bool {
+        card.rank >= self.lowest_rank
+    }
+
+    // Where a category, numbered like Hand's Into<u8>, ranks under these rules: 0 for the worst, up to NUM_HANDS - 1 for the best.
+    pub fn category_strength(&self, category: u8) -> usize {
+        // create made sure every category is in the order.
+        self.category_order.iter().position(|&other| other == category).unwrap()
+    }
+
+    // Like Hand's Ord, but with these rules' category order.
+    pub fn cmp_hands(&self, this: &Hand, other: &Hand) -> Ordering {
+        let this_strength = self.category_strength(u8::from(*this));
+        let other_strength = self.category_strength(u8::from(*other));
+        if this_strength != other_strength {
+            return this_strength.cmp(&other_strength);
+        }
+        cmp_same_type_hand(this, other)
+    }
+
+    // Like Hand::get_hand, with these rules' wheel. Cards outside the deck are an error.
+    pub fn get_hand<I>(&self, cards: I) -> Result<Hand, InvalidHandError> where I: IntoIterator, I::Item: Borrow<Card> {
+        let cards: Vec<Card> = cards.into_iter().map(|card| *card.borrow()).collect();
+        if cards.len() != 5 {
+            return Err(InvalidHandError::WrongNumberOfCards{expected: 5, found: cards.len()});
+        }
+        let cards = self.sorted_cards_in_deck(cards)?;
+        Ok(Hand::make_hand_with_wheel(&cards, self.wheel_top))
+    }
+
+    // Like Hand::best_hand_of, comparing hands with cmp_hands.
+    pub fn best_hand_of<I>(&self, cards: I) -> Result<Hand, InvalidHandError> where I: IntoIterator, I::Item: Borrow<Card> {
+        self.best_hand_with_cards(cards).map(|best_hand| best_hand.hand)
+    }
+
+    // Hand::best_hand_with_cards is this with the standard rules.
+    pub fn best_hand_with_cards<I>(&self, cards: I) -> Result<BestHand, InvalidHandError> where I: IntoIterator, I::Item: Borrow<Card> {
+        let cards: Vec<Card> = cards.into_iter().map(|card| *card.borrow()).collect();
+        if cards.len() < 5 {
+            return Err(InvalidHandError::TooFewCards{needed: 5, found: cards.len()});
+        }
+        let cards = self.sorted_cards_in_deck(cards)?;
+
+        let mut best_hand: Option<BestHand> = None;
+        for five_cards in AllFiveCardSubsets::create(&cards) {
+            let this_hand = Hand::make_hand_with_wheel(&five_cards, self.wheel_top);
+            let better = match best_hand {
+                Some(BestHand{hand, ..}) => self.cmp_hands(&this_hand, &hand) == Ordering::Greater,
+                None => true,
+            };
+            if better {
+                best_hand = Some(BestHand{hand: this_hand, cards: five_cards});
+            }
+        }
+        Ok(best_hand.unwrap())
+    }
+
+    // The first card that isn't dealt under these rules is an error.
+    pub(crate) fn check_in_deck<'a, I: IntoIterator<Item=&'a Card>>(&self, cards: I) -> Result<(), InvalidHandError> {
+        match cards.into_iter().find(|&&card| !self.in_deck(card)) {
+            Some(&card) => Err(InvalidHandError::NotInDeck(card)),
+            None => Ok(()),
+        }
+    }
+
+    fn sorted_cards_in_deck(&self, cards: Vec<Card>) -> Result<Vec<Card>, InvalidHandError> {
+        self.check_in_deck(&cards)?;
+        sorted_cards(cards)
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Rules {
+        Rules::standard()
+    }
+}
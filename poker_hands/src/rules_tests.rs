@@ -0,0 +1,116 @@
+#![cfg(test)]
+
+use std::cmp::Ordering;
+use rand::{Rng, SeedableRng, XorShiftRng};
+use super::*;
+use super::Hand::*;
+use super::evaluator::*;
+use test_helpers::cards;
+
+use cards::Card;
+use cards::Rank::*;
+
+#[test]
+fn short_deck_has_thirty_six_cards() {
+    let rules = Rules::short_deck();
+    assert_eq!(36, rules.deck_size());
+    assert!(rules.deck().iter().all(|card| card.rank >= Six));
+    assert_eq!(52, Rules::standard().deck_size());
+    assert_eq!(Err(InvalidHandError::NotInDeck(cards("5s")[0])), rules.get_hand(cards("AsKsQsJs5s")));
+}
+
+#[test]
+fn short_deck_flushes_beat_full_houses() {
+    let rules = Rules::short_deck();
+    let flush = rules.get_hand(cards("AsQs9s8s6s")).unwrap();
+    let full_house = rules.get_hand(cards("AsAdAhKcKs")).unwrap();
+    assert_eq!(Ordering::Greater, rules.cmp_hands(&flush, &full_house));
+    assert_eq!(Ordering::Less, Rules::standard().cmp_hands(&flush, &full_house));
+    assert!(flush < full_house);
+
+    let flush_cards = cards("AsQs9s8s6s");
+    let full_house_cards = cards("AsAdAhKcKs");
+    assert!(evaluate_with_rules(&flush_cards, &rules).unwrap() < evaluate_with_rules(&full_house_cards, &rules).unwrap());
+    assert_eq!(5, category_with_rules(evaluate_with_rules(&flush_cards, &rules).unwrap(), &rules).unwrap());
+    assert_eq!(6, category_with_rules(evaluate_with_rules(&full_house_cards, &rules).unwrap(), &rules).unwrap());
+}
+
+#[test]
+fn short_deck_ace_plays_low_under_six() {
+    let rules = Rules::short_deck();
+    let wheel = rules.best_hand_of(cards("Ah6s7d8c9hKd")).unwrap();
+    assert_eq!(Ordering::Equal, rules.cmp_hands(&Straight(StraightStr{hi_rank: Nine}), &wheel));
+    assert_eq!(Ordering::Less, rules.cmp_hands(&wheel, &rules.get_hand(cards("6s7d8c9hTh")).unwrap()));
+    // A-6-7-8-9 isn't a straight with a full deck.
+    assert_eq!(Ordering::Equal, Rules::standard().cmp_hands(&HiCard(HiCardStr{ranks: [Ace, Nine, Eight, Seven, Six]}), &Hand::get_hand(cards("Ah6s7d8c9h")).unwrap()));
+
+    let straight_flush = evaluate_with_rules(&cards("Ah6h7h8h9h"), &rules).unwrap();
+    assert_eq!(8, category_with_rules(straight_flush, &rules).unwrap());
+    assert!(evaluate_with_rules(&cards("6h7h8h9hTh"), &rules).unwrap() < straight_flush);
+}
+
+#[test]
+fn evaluator_agrees_with_rules() {
+    let mut rng = XorShiftRng::from_seed([4, 3, 2, 1]);
+    for rules in &[Rules::standard(), Rules::short_deck()] {
+        let mut deck: Vec<Card> = rules.deck().iter().collect();
+        let mut previous: Option<(Hand, u16)> = None;
+        for _ in 0..5000 {
+            rng.shuffle(&mut deck);
+            let cards = &deck[..7];
+            let hand = rules.best_hand_of(cards).unwrap();
+            let rank = evaluate_with_rules(cards, rules).unwrap();
+            assert_eq!(u8::from(hand), category_with_rules(rank, rules).unwrap());
+            if let Some((previous_hand, previous_rank)) = previous {
+                // Lower ranks are better hands.
+                assert_eq!(previous_rank.cmp(&rank), rules.cmp_hands(&hand, &previous_hand),
+                           "{:?} (rank {}) vs {:?} (rank {})", hand, rank, previous_hand, previous_rank);
+            }
+            previous = Some((hand, rank));
+        }
+    }
+}
+
+#[test]
+fn evaluator_follows_custom_orders() {
+    // Short deck where trips beat straights.
+    let trips_over_straights = Rules::create(Six, [0, 1, 2, 4, 3, 6, 5, 7, 8]).unwrap();
+    let straight_and_trips = cards("6s7d8c9hTsThTc");
+    assert_eq!(Trips(TripsStr{rank: Ten, kickers: [Nine, Eight]}), trips_over_straights.best_hand_of(&straight_and_trips).unwrap());
+    let rank = evaluate_with_rules(&straight_and_trips, &trips_over_straights).unwrap();
+    assert_eq!(3, category_with_rules(rank, &trips_over_straights).unwrap());
+
+    // Worst to best upside down, so every category the cards make is in play.
+    let upside_down = Rules::create(Two, [8, 7, 6, 5, 4, 3, 2, 1, 0]).unwrap();
+    let mut rng = XorShiftRng::from_seed([1, 8, 1, 8]);
+    for rules in &[trips_over_straights, upside_down] {
+        let mut deck: Vec<Card> = rules.deck().iter().collect();
+        for _ in 0..2000 {
+            rng.shuffle(&mut deck);
+            let cards = &deck[..7];
+            let hand = rules.best_hand_of(cards).unwrap();
+            let rank = evaluate_with_rules(cards, rules).unwrap();
+            assert_eq!(u8::from(hand), category_with_rules(rank, rules).unwrap(), "{:?} in {:?}", hand, cards);
+            let five_cards = rules.best_hand_with_cards(cards).unwrap().cards;
+            assert_eq!(evaluate_with_rules(&five_cards, rules).unwrap(), rank, "{:?} in {:?}", hand, cards);
+        }
+    }
+}
+
+#[test]
+fn rules_are_checked() {
+    assert_eq!(Ok(Rules::short_deck()), Rules::create(Six, [0, 1, 2, 3, 4, 6, 5, 7, 8]));
+    assert_eq!(Nine, Rules::create(Six, [0, 1, 2, 3, 4, 5, 6, 7, 8]).unwrap().wheel_top());
+    assert_eq!(Five, Rules::standard().wheel_top());
+    assert_eq!(Err(InvalidRulesError::NotACategoryOrder([0, 1, 2, 3, 4, 5, 6, 7, 7])), Rules::create(Two, [0, 1, 2, 3, 4, 5, 6, 7, 7]));
+    assert_eq!(Err(InvalidRulesError::NotACategoryOrder([0, 1, 2, 3, 4, 5, 6, 7, 9])), Rules::create(Two, [0, 1, 2, 3, 4, 5, 6, 7, 9]));
+    assert_eq!(Err(InvalidRulesError::TooFewRanks(Ten)), Rules::create(Ten, [0, 1, 2, 3, 4, 5, 6, 7, 8]));
+}
+
+#[test]
+fn ranks_and_hands_reject_the_same_cards() {
+    let rules = Rules::short_deck();
+    let two = cards("2c")[0];
+    assert_eq!(Err(InvalidHandError::NotInDeck(two)), evaluate_with_rules(&cards("AsKsQsJs2c"), &rules));
+    assert_eq!(Err(InvalidHandError::NotInDeck(two)), rules.best_hand_of(cards("AsKsQsJs2c")));
+}
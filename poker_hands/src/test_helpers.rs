@@ -0,0 +1,15 @@
+#![cfg(test)]
+
+// Helpers shared by the tests in this crate.
+
+use std::fmt::Debug;
+
+use cards::{Card, parse_cards};
+
+pub fn cards(cards_string: &str) -> Vec<Card> {
+    parse_cards(cards_string).unwrap()
+}
+
+pub fn assert_equal<T: Eq + Debug>(expected: &T, actual: &T) {
+    assert!(expected == actual, "Expected {:?} but got {:?}", expected, actual)
+}
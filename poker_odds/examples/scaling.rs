@@ -0,0 +1,59 @@
+/*
+How the simulator speeds up with more threads. Runs the same hold'em and
+Omaha calculations with 1, 2, 4 and so on up to one thread per CPU, and
+prints how long each took and how much faster that was than one thread.
+Threads only share anything when they merge their tallies at the end, so on
+an otherwise idle machine the speedup should be close to the number of
+threads, up to the number of cores.
+
+    cargo run --release --example scaling [num_sims]
+*/
+
+extern crate num_cpus;
+extern crate cards;
+extern crate poker_odds;
+
+use std::env;
+use std::time::Instant;
+
+use cards::parse_cards;
+use poker_odds::{EquityCalculator, Game, SimulationMode};
+
+fn main() {
+    let num_sims = env::args().nth(1).and_then(|arg| arg.parse().ok()).unwrap_or(2000 * 1000);
+    let max_threads = num_cpus::get() as i32;
+
+    let mut calculators = vec![
+        ("Hold'em", calculator(Game::Holdem, &["AsKs", "QdQh", "JcTc"], num_sims)),
+        ("Omaha", calculator(Game::Omaha, &["AsKsQdJd", "7h8h9cTc"], num_sims / 5)),
+    ];
+    for &mut (name, ref mut calculator) in &mut calculators {
+        println!("{}, {} hands:", name, calculator.num_sims);
+        let mut one_thread_seconds = 0f64;
+        let mut num_threads = 1;
+        while num_threads <= max_threads {
+            let seconds = time_run(calculator, num_threads);
+            if num_threads == 1 {
+                one_thread_seconds = seconds;
+            }
+            println!("\t{} threads: {:.3}s, {:.2}x", num_threads, seconds, one_thread_seconds / seconds);
+            num_threads = if num_threads * 2 > max_threads && num_threads < max_threads { max_threads } else { num_threads * 2 };
+        }
+    }
+}
+
+fn calculator(game: Game, hand_strings: &[&str], num_sims: i32) -> EquityCalculator {
+    let hole_cards: Vec<_> = hand_strings.iter().map(|hand_string| parse_cards(hand_string).unwrap()).collect();
+    let mut calculator = EquityCalculator::create(hole_cards, Vec::new(), num_sims);
+    calculator.game = game;
+    calculator.mode = SimulationMode::Random;
+    calculator
+}
+
+fn time_run(calculator: &mut EquityCalculator, num_threads: i32) -> f64 {
+    calculator.num_threads = num_threads;
+    let start = Instant::now();
+    calculator.run().unwrap();
+    let elapsed = start.elapsed();
+    elapsed.as_secs() as f64 + f64::from(elapsed.subsec_nanos()) / 1e9
+}
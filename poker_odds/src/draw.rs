@@ -0,0 +1,97 @@
+/*
+Five-card draw, where each player throws away some of their five cards and
+is dealt as many replacements from the deck. There's only one draw.
+*/
+
+use cards::{Card, Rank};
+use poker_hands::{InvalidHandError, Rules};
+use poker_hands::Hand::*;
+
+pub const DRAW_HAND_SIZE: usize = 5;
+
+// The most cards Discard::Auto ever throws away, since it always keeps at least one.
+const MAX_AUTO_DRAWS: usize = DRAW_HAND_SIZE - 1;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Discard {
+    // Keeps all five cards.
+    StandPat,
+    // Throws away exactly these cards, so the player's hand has to be known.
+    Cards(Vec<Card>),
+    // Picks the discards from whatever was dealt, like cards_to_keep does.
+    Auto,
+}
+
+impl Discard {
+    // The most cards this can throw away.
+    pub fn max_draws(&self) -> usize {
+        match *self {
+            Discard::StandPat => 0,
+            Discard::Cards(ref cards) => cards.len(),
+            Discard::Auto => MAX_AUTO_DRAWS,
+        }
+    }
+
+    // Whether each card in the hand is kept.
+    pub fn kept(&self, hand: &[Card], rules: &Rules) -> Result<[bool; DRAW_HAND_SIZE], InvalidHandError> {
+        if hand.len() != DRAW_HAND_SIZE {
+            return Err(InvalidHandError::WrongNumberOfCards{expected: DRAW_HAND_SIZE, found: hand.len()});
+        }
+        match *self {
+            Discard::StandPat => Ok([true; DRAW_HAND_SIZE]),
+            Discard::Cards(ref cards) => Ok(kept_where(hand, |card| !cards.contains(card))),
+            Discard::Auto => auto_kept(hand, rules),
+        }
+    }
+}
+
+/*
+A simple rule of thumb for which cards to keep: stand pat with a straight or
+better, and keep the matched cards of anything from a pair to trips.
+Without a pair, keep four to a flush or to an open-ended straight, or else
+just the highest card.
+*/
+pub fn cards_to_keep(hand: &[Card], rules: &Rules) -> Result<Vec<Card>, InvalidHandError> {
+    let kept = auto_kept(hand, rules)?;
+    Ok(hand.iter().zip(&kept).filter(|&(_, &keep)| keep).map(|(&card, _)| card).collect())
+}
+
+// The hand has to be a valid five-card hand under the rules.
+fn auto_kept(hand: &[Card], rules: &Rules) -> Result<[bool; DRAW_HAND_SIZE], InvalidHandError> {
+    let kept = match rules.get_hand(hand)? {
+        Straight(..) | Flush(..) | FullHouse(..) | Quads(..) | StraightFlush(..) => [true; DRAW_HAND_SIZE],
+        Trips(trips) => kept_where(hand, |card| card.rank == trips.rank),
+        TwoPair(two_pair) => kept_where(hand, |card| card.rank == two_pair.hi_rank || card.rank == two_pair.lo_rank),
+        Pair(pair) => kept_where(hand, |card| card.rank == pair.rank),
+        HiCard(..) => {
+            let four_flush = hand.iter().map(|card| card.suit).find(|&suit| hand.iter().filter(|card| card.suit == suit).count() == 4);
+            if let Some(suit) = four_flush {
+                return Ok(kept_where(hand, |card| card.suit == suit));
+            }
+            if let Some(low_rank) = open_ended_low_rank(hand) {
+                return Ok(kept_where(hand, |card| card.rank >= low_rank && card.rank as u8 <= low_rank as u8 + 3));
+            }
+            let highest = hand.iter().map(|card| card.rank).max().unwrap();
+            kept_where(hand, |card| card.rank == highest)
+        },
+    };
+    Ok(kept)
+}
+
+fn kept_where<F: Fn(&Card) -> bool>(hand: &[Card], is_kept: F) -> [bool; DRAW_HAND_SIZE] {
+    let mut kept = [false; DRAW_HAND_SIZE];
+    for (keep, card) in kept.iter_mut().zip(hand) {
+        *keep = is_kept(card);
+    }
+    kept
+}
+
+// The lowest rank of four cards in a row that a card at either end would make a straight, if there are any. The ranks must all be different.
+fn open_ended_low_rank(hand: &[Card]) -> Option<Rank> {
+    let mut ranks: Vec<Rank> = hand.iter().map(|card| card.rank).collect();
+    ranks.sort();
+    // With an ace on top, only the card below can fill it.
+    ranks.windows(4).
+        find(|four| four[3] as u8 == four[0] as u8 + 3 && four[3] != Rank::Ace).
+        map(|four| four[0])
+}
@@ -0,0 +1,46 @@
+#![cfg(test)]
+
+use super::draw::*;
+use test_helpers::cards;
+
+use cards::Card;
+use poker_hands::{InvalidHandError, Rules};
+
+fn kept(hand_string: &str) -> Vec<Card> {
+    cards_to_keep(&cards(hand_string), &Rules::standard()).unwrap()
+}
+
+#[test]
+fn made_hands_stand_pat() {
+    assert_eq!(cards("9s8d7h6c5s"), kept("9s8d7h6c5s"));
+    assert_eq!(cards("Ks9s7s4s2s"), kept("Ks9s7s4s2s"));
+    assert_eq!(cards("KsKdKh4c4s"), kept("KsKdKh4c4s"));
+}
+
+#[test]
+fn matched_cards_are_kept() {
+    assert_eq!(cards("KsKdKh"), kept("KsKdKh9c4s"));
+    assert_eq!(cards("KsKd4c4s"), kept("KsKd9h4c4s"));
+    assert_eq!(cards("4c4s"), kept("AsKd9h4c4s"));
+}
+
+#[test]
+fn draws_are_kept_without_a_pair() {
+    assert_eq!(cards("Ks9s7s2s"), kept("Ks9s7sAd2s"));
+    assert_eq!(cards("9s8d7h6c"), kept("9s8d7h6cKs"));
+    assert_eq!(cards("5s4d3h2c"), kept("Js5s4d3h2c"));
+    // Ace-high and ace-low draws only have one way in.
+    assert_eq!(cards("As"), kept("AsKdQhJc2s"));
+    assert_eq!(cards("Ac"), kept("Ks4s3d2hAc"));
+}
+
+#[test]
+fn discarding_exact_cards() {
+    let hand = cards("AsAdKh7c2s");
+    let discard = Discard::Cards(cards("7c2s"));
+    assert_eq!(Ok([true, true, true, false, false]), discard.kept(&hand, &Rules::standard()));
+    assert_eq!(2, discard.max_draws());
+    assert_eq!(Ok([true; DRAW_HAND_SIZE]), Discard::StandPat.kept(&hand, &Rules::standard()));
+    assert_eq!(Err(InvalidHandError::WrongNumberOfCards{expected: 5, found: 4}), Discard::Auto.kept(&hand[..4], &Rules::standard()));
+    assert_eq!(Err(InvalidHandError::DuplicateCard(hand[0])), cards_to_keep(&[hand[0], hand[0], hand[1], hand[2], hand[3]], &Rules::standard()));
+}
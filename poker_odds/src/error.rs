@@ -0,0 +1,76 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use cards::Card;
+use poker_hands::InvalidHandError;
+
+// Reasons an equity calculation can't be run.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SimulationError {
+    NoPlayers,
+    NoThreads,
+    TooManyBoardCards{allowed: usize, found: usize},
+    // The same card is on the board, dead or in someone's known hole cards more than once.
+    DuplicateCard(Card),
+    // A card on the board, dead or in someone's known hole cards isn't in the deck for the rules being played.
+    NotInDeck(Card),
+    // There aren't enough cards in the deck to deal everyone in.
+    NotEnoughCards{num_players: usize},
+    // This player's hole cards, or some of the combos in their range, have the wrong number of cards for the game. In stud, that's more than expected.
+    WrongNumberOfHoleCards{player: usize, expected: usize, found: usize},
+    // Every combo in this player's range uses cards that are already out of the deck.
+    EmptyRange{player: usize},
+    // The ranges overlap so much that hole cards for everyone couldn't be dealt from them.
+    RangesClash,
+    // Enumerating boards needs everyone's hole cards to be known.
+    UnknownHoleCards,
+    // Discards were given for a game without a draw.
+    NotADrawGame,
+    // This player discards cards that aren't in their known hand, or discards one twice.
+    InvalidDiscard{player: usize},
+    // The precision to stop at needs a margin above 0 and a confidence between 0 and 1.
+    InvalidPrecision,
+    // num_sims has to be at least 1.
+    NoSimulations,
+    // Evaluating someone's hand failed, which validation should have ruled out.
+    InvalidHand(InvalidHandError),
+}
+
+impl Display for SimulationError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match *self {
+            SimulationError::NoPlayers => write!(f, "no hole cards specified"),
+            SimulationError::NoThreads => write!(f, "need at least one thread"),
+            SimulationError::TooManyBoardCards{allowed, found} =>
+                write!(f, "the board can have at most {} cards, not {}", allowed, found),
+            SimulationError::DuplicateCard(card) => write!(f, "{} is used more than once", card),
+            SimulationError::NotInDeck(card) => write!(f, "{} isn't in the deck", card),
+            SimulationError::NotEnoughCards{num_players} =>
+                write!(f, "there aren't enough cards to deal to {} players", num_players),
+            SimulationError::WrongNumberOfHoleCards{player, expected, found} =>
+                write!(f, "hand {} should have {} hole cards, not {}", player, expected, found),
+            SimulationError::EmptyRange{player} =>
+                write!(f, "every combo in hand {}'s range is already in use", player),
+            SimulationError::RangesClash =>
+                write!(f, "couldn't deal hole cards from the ranges without using a card twice"),
+            SimulationError::UnknownHoleCards =>
+                write!(f, "can only enumerate boards when all hole cards are known"),
+            SimulationError::NotADrawGame =>
+                write!(f, "can only discard in a draw game"),
+            SimulationError::InvalidDiscard{player} =>
+                write!(f, "hand {} can only discard cards from its own known hand", player),
+            SimulationError::InvalidPrecision =>
+                write!(f, "the precision needs a margin above 0 and a confidence between 0 and 1"),
+            SimulationError::NoSimulations => write!(f, "need to simulate at least one hand"),
+            SimulationError::InvalidHand(error) => write!(f, "invalid hand: {}", error),
+        }
+    }
+}
+
+impl Error for SimulationError {}
+
+impl From<InvalidHandError> for SimulationError {
+    fn from(error: InvalidHandError) -> SimulationError {
+        SimulationError::InvalidHand(error)
+    }
+}
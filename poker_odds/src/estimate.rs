@@ -0,0 +1,103 @@
+/*
+How precise the simulated numbers are. Each simulated hand is an
+independent draw, so a fraction of hands (or of pots won) has a standard
+error of the standard deviation of one hand's result over the square root of
+the number of hands. Intervals use the normal approximation, which is good
+for the thousands of hands simulations usually run. Enumerated results are
+exact, with no error at all.
+*/
+
+pub const DEFAULT_CONFIDENCE: f64 = 0.95;
+
+// How well an estimate should be known: to within margin either side, with the given confidence, like 0.001 at 0.95.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Precision {
+    pub margin: f64,
+    pub confidence: f64,
+}
+
+impl Precision {
+    pub fn create(margin: f64, confidence: f64) -> Precision {
+        Precision{margin, confidence}
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.margin > 0f64 && self.confidence > 0f64 && self.confidence < 1f64
+    }
+
+    pub fn is_met_by(&self, estimate: &Estimate) -> bool {
+        estimate.margin(self.confidence) <= self.margin
+    }
+}
+
+// An estimated fraction of hands, from 0 to 1.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Estimate {
+    pub value: f64,
+    pub standard_error: f64,
+}
+
+impl Estimate {
+    // From how often something happened, out of num_sims hands.
+    pub fn of_proportion(num_events: i32, num_sims: i32, exact: bool) -> Estimate {
+        let value = num_events as f64 / num_sims as f64;
+        Estimate::of_mean(value, value, num_sims, exact)
+    }
+
+    // From the mean and the mean of the squares of a result from 0 to 1 for each of num_sims hands.
+    pub fn of_mean(mean: f64, mean_of_squares: f64, num_sims: i32, exact: bool) -> Estimate {
+        if exact {
+            return Estimate{value: mean, standard_error: 0f64};
+        }
+        // Rounding can take the variance just below zero when every hand had the same result.
+        let variance = (mean_of_squares - mean * mean).max(0f64);
+        Estimate{value: mean, standard_error: (variance / num_sims as f64).sqrt()}
+    }
+
+    // Half the width of the confidence interval.
+    pub fn margin(&self, confidence: f64) -> f64 {
+        z_score(confidence) * self.standard_error
+    }
+
+    // The range the true value is in with the given confidence, like 0.95. Fractions can't go outside 0 to 1.
+    pub fn confidence_interval(&self, confidence: f64) -> (f64, f64) {
+        let margin = self.margin(confidence);
+        ((self.value - margin).max(0f64), (self.value + margin).min(1f64))
+    }
+}
+
+// How many standard errors either side of the mean cover the given fraction of a normal distribution.
+pub fn z_score(confidence: f64) -> f64 {
+    assert!(confidence > 0f64 && confidence < 1f64, "Confidence must be between 0 and 1, not {}", confidence);
+    normal_quantile(1f64 - (1f64 - confidence) / 2f64)
+}
+
+/*
+The inverse of the standard normal distribution function, using Peter
+Acklam's rational approximations, which have a relative error under
+1.15e-9 everywhere.
+*/
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [-3.969683028665376e+01, 2.209460984245205e+02, -2.759285104469687e+02,
+                         1.38357751867269e+02, -3.066479806614716e+01, 2.506628277459239e+00];
+    const B: [f64; 5] = [-5.447609879822406e+01, 1.615858368580409e+02, -1.556989798598866e+02,
+                         6.680131188771972e+01, -1.328068155288572e+01];
+    const C: [f64; 6] = [-7.784894002430293e-03, -3.223964580411365e-01, -2.400758277161838e+00,
+                         -2.549732539343734e+00, 4.374664141464968e+00, 2.938163982698783e+00];
+    const D: [f64; 4] = [7.784695709041462e-03, 3.224671290700398e-01, 2.445134137142996e+00,
+                         3.754408661907416e+00];
+    const LOW_TAIL: f64 = 0.02425;
+
+    if p < LOW_TAIL {
+        let q = (-2f64 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5]) /
+            ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1f64)
+    } else if p <= 1f64 - LOW_TAIL {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q /
+            (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1f64)
+    } else {
+        -normal_quantile(1f64 - p)
+    }
+}
@@ -0,0 +1,35 @@
+#![cfg(test)]
+
+use super::estimate::*;
+
+fn assert_close(expected: f64, actual: f64) {
+    assert!((expected - actual).abs() < 1e-6, "Expected {} but got {}", expected, actual);
+}
+
+#[test]
+fn z_scores_for_common_confidences() {
+    assert_close(1.959964, z_score(0.95));
+    assert_close(2.575829, z_score(0.99));
+    assert_close(1.644854, z_score(0.9));
+    assert_close(3.290527, z_score(0.999));
+}
+
+#[test]
+fn proportions_have_binomial_errors() {
+    let estimate = Estimate::of_proportion(2500, 10000, false);
+    assert_close(0.25, estimate.value);
+    assert_close((0.25f64 * 0.75 / 10000f64).sqrt(), estimate.standard_error);
+    let (low, high) = estimate.confidence_interval(0.95);
+    assert_close(0.25 - 1.959964 * estimate.standard_error, low);
+    assert_close(0.25 + 1.959964 * estimate.standard_error, high);
+
+    assert_eq!(0f64, Estimate::of_proportion(2500, 10000, true).standard_error);
+    assert_eq!((0f64, 0f64), Estimate::of_proportion(0, 100, false).confidence_interval(0.95));
+}
+
+#[test]
+fn chops_vary_less_than_wins() {
+    // Chopping every pot in half never varies, unlike winning half the pots outright.
+    assert_eq!(0f64, Estimate::of_mean(0.5, 0.25, 1000, false).standard_error);
+    assert!(Estimate::of_mean(0.5, 0.5, 1000, false).standard_error > 0f64);
+}
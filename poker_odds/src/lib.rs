@@ -0,0 +1,967 @@
+extern crate rand;
+extern crate cards;
+extern crate poker_hands;
+
+mod tests;
+mod range_tests;
+mod draw_tests;
+mod estimate_tests;
+mod test_helpers;
+mod range;
+mod error;
+pub mod draw;
+pub mod estimate;
+
+pub use range::{HandRange, RangeError, WeightedCombo};
+pub use error::SimulationError;
+pub use draw::Discard;
+pub use estimate::{Estimate, Precision, DEFAULT_CONFIDENCE};
+use range::ComboSampler;
+
+use std::cmp::{self, Reverse};
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+use std::thread;
+use std::sync::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use rand::{Rng, SeedableRng, XorShiftRng};
+
+use cards::{Card, CardSet, Deck, card};
+use cards::Rank::*;
+use cards::Suit::*;
+use poker_hands::{InvalidHandError, NUM_HANDS, Rules};
+use poker_hands::{evaluator, omaha, low};
+use poker_hands::low::LowHand;
+use draw::DRAW_HAND_SIZE;
+
+// The size of a full board in the games that have one.
+pub const BOARD_SIZE: usize = 5;
+// How many cards each player gets in seven-card stud.
+pub const STUD_HAND_SIZE: usize = 7;
+
+// Auto mode enumerates every board when there are at most this many of them.
+pub const EXACT_THRESHOLD: i32 = 100 * 1000;
+
+// When stopping early, each thread merges what it's simulated and checks whether to stop this often.
+pub const SIMS_PER_MERGE: i32 = 1000;
+// Estimates from fewer hands than this can look precise just by luck, like when every hand so far had the same winner.
+pub const MIN_SIMS_FOR_PRECISION: i32 = 10 * 1000;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SimulationMode {
+    // Exact if there are few enough boards left, otherwise random.
+    Auto,
+    // Deal num_sims random boards.
+    Random,
+    // Deal every possible board exactly once.
+    Exact,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Game {
+    Holdem,
+    // Hands are made from exactly two hole cards and three board cards.
+    Omaha,
+    FiveCardOmaha,
+    SixCardOmaha,
+    // No board; each player gets their own seven cards, some of them face up.
+    SevenCardStud,
+    // No board; each player gets five cards and can swap some of them once.
+    FiveCardDraw,
+}
+
+impl Game {
+    // How many cards each player has by the showdown, up and down alike.
+    pub fn num_hole_cards(&self) -> usize {
+        match *self {
+            Game::Holdem => 2,
+            Game::Omaha => 4,
+            Game::FiveCardOmaha => 5,
+            Game::SixCardOmaha => 6,
+            Game::SevenCardStud => STUD_HAND_SIZE,
+            Game::FiveCardDraw => DRAW_HAND_SIZE,
+        }
+    }
+
+    pub fn board_size(&self) -> usize {
+        match *self {
+            Game::Holdem | Game::Omaha | Game::FiveCardOmaha | Game::SixCardOmaha => BOARD_SIZE,
+            Game::SevenCardStud | Game::FiveCardDraw => 0,
+        }
+    }
+
+    // Whether players can start with only some of their cards known, and get the rest dealt to them.
+    pub fn deals_hole_cards(&self) -> bool {
+        *self == Game::SevenCardStud
+    }
+
+    // Ranks a player's hand on a complete board, like evaluator::evaluate_with_rules does.
+    fn evaluate(&self, hole_cards: &[Card], board: &[Card], rules: &Rules) -> Result<u16, InvalidHandError> {
+        match *self {
+            Game::Holdem => {
+                let mut cards = [card(Ace, Spades); BOARD_SIZE + 2]; // Dummies
+                cards[..BOARD_SIZE].copy_from_slice(board);
+                cards[BOARD_SIZE..].copy_from_slice(hole_cards);
+                evaluator::evaluate_with_rules(&cards, rules)
+            },
+            Game::Omaha | Game::FiveCardOmaha | Game::SixCardOmaha => omaha::evaluate_with_rules(hole_cards, board, rules),
+            // The same as the best hand of the seven cards, or Hand::get_hand for draw, just faster.
+            Game::SevenCardStud | Game::FiveCardDraw => evaluator::evaluate_with_rules(hole_cards, rules),
+        }
+    }
+
+    // The player's eight-or-better low on a complete board, if they have one.
+    fn evaluate_low(&self, hole_cards: &[Card], board: &[Card]) -> Option<LowHand> {
+        match *self {
+            Game::Holdem => {
+                let mut cards = [card(Ace, Spades); BOARD_SIZE + 2]; // Dummies
+                cards[..BOARD_SIZE].copy_from_slice(board);
+                cards[BOARD_SIZE..].copy_from_slice(hole_cards);
+                low::best_low_of(&cards)
+            },
+            Game::Omaha | Game::FiveCardOmaha | Game::SixCardOmaha => low::best_omaha_low(hole_cards, board),
+            Game::SevenCardStud | Game::FiveCardDraw => low::best_low_of(hole_cards),
+        }
+    }
+}
+
+// Gives up on dealing hole cards from ranges after this many clashes in a row.
+const MAX_DEALING_ATTEMPTS: i32 = 10 * 1000;
+
+// Works out how often each player's hole cards win by dealing out the rest of the board.
+pub struct EquityCalculator {
+    pub game: Game,
+    // Which cards are in the deck and how hands rank, like for short deck.
+    pub rules: Rules,
+    // Whether the pot is split between the best high hand and the best eight-or-better low.
+    pub hi_lo: bool,
+    /*
+    One entry per player. A player whose hole cards are known has a range of
+    one combo. In stud, a combo is just the cards known so far, like an
+    opponent's upcards, and the rest are dealt from the deck.
+    */
+    pub ranges: Vec<HandRange>,
+    // Cards already on the board. At most the game's board size of them.
+    pub board: Vec<Card>,
+    // Cards known to be out of play, like folded or exposed cards. They're never dealt.
+    pub dead_cards: Vec<Card>,
+    // Players whose hole cards are dealt at random each hand. They come after the ranges.
+    pub num_random_opponents: usize,
+    // Only in draw: what each player with a range throws away. Players past the end of this, including random opponents, use Discard::Auto.
+    pub discards: Vec<Discard>,
+    // How many hands to simulate, or at most how many when stopping early.
+    pub num_sims: i32,
+    pub num_threads: i32,
+    pub mode: SimulationMode,
+    // Stop simulating once every player's equity is this precise. Like time_limit, ignored when is_exact().
+    pub precision: Option<Precision>,
+    // Stop simulating after about this long.
+    pub time_limit: Option<Duration>,
+    /*
+    Seeds each thread's random numbers, so that running again with the same
+    inputs, seed and number of threads gives exactly the same results. That
+    can't hold when stopping at a time limit, or at a precision with more
+    than one thread, since then it matters how fast the threads go. Without
+    a seed, every run is different.
+    */
+    pub seed: Option<u64>,
+}
+
+impl EquityCalculator {
+    pub fn create<C: AsRef<[Card]>>(hole_cards: Vec<C>, board: Vec<Card>, num_sims: i32) -> EquityCalculator {
+        let ranges = hole_cards.into_iter().map(HandRange::from_cards).collect();
+        EquityCalculator::create_with_ranges(ranges, board, num_sims)
+    }
+
+    pub fn create_with_ranges(ranges: Vec<HandRange>, board: Vec<Card>, num_sims: i32) -> EquityCalculator {
+        EquityCalculator{game: Game::Holdem, rules: Rules::standard(), hi_lo: false, ranges, board, dead_cards: Vec::new(), num_random_opponents: 0, discards: Vec::new(), num_sims, num_threads: 1, mode: SimulationMode::Auto, precision: None, time_limit: None, seed: None}
+    }
+
+    pub fn num_players(&self) -> usize {
+        self.ranges.len() + self.num_random_opponents
+    }
+
+    // Everyone's hole cards, if they're all known exactly.
+    pub fn known_hole_cards(&self) -> Option<Vec<Vec<Card>>> {
+        if self.num_random_opponents > 0 {
+            return None;
+        }
+        // Nobody's hand is known until after the draw.
+        if (0..self.num_players()).any(|player| self.discard_of(player).max_draws() > 0) {
+            return None;
+        }
+        self.ranges.iter().map(|range| {
+            if range.num_combos() == 1 && range.combos()[0].cards.len() == self.game.num_hole_cards() {
+                Some(range.combos()[0].cards.clone())
+            } else {
+                None
+            }
+        }).collect()
+    }
+
+    // How many different ways the rest of the board can come out.
+    pub fn num_board_completions(&self) -> u64 {
+        // Too many board cards fails validation, but this can be asked before then.
+        if self.board.len() > self.game.board_size() {
+            return 0;
+        }
+        let num_unseen = self.rules.deck_size().saturating_sub(self.board.len() + self.dead_cards.len() + self.game.num_hole_cards() * self.num_players());
+        num_combinations(num_unseen, self.game.board_size() - self.board.len())
+    }
+
+    pub fn is_exact(&self) -> bool {
+        match self.mode {
+            // Enumerating boards only makes sense when the hole cards don't change.
+            SimulationMode::Auto => self.known_hole_cards().is_some() && self.num_board_completions() <= EXACT_THRESHOLD as u64,
+            SimulationMode::Random => false,
+            SimulationMode::Exact => true,
+        }
+    }
+
+    // Whether run() can stop before simulating num_sims hands. Enumerating never stops early.
+    pub fn stops_early(&self) -> bool {
+        (self.precision.is_some() || self.time_limit.is_some()) && !self.is_exact()
+    }
+
+    // How many hands run() will simulate, or at most how many if it stops early.
+    pub fn total_num_sims(&self) -> i32 {
+        if self.is_exact() {
+            self.num_board_completions() as i32
+        } else if self.board.len() == self.game.board_size() && self.known_hole_cards().is_some() {
+            // With a full board and known hole cards there's nothing left to chance.
+            1
+        } else {
+            self.num_sims
+        }
+    }
+
+    // Checks that the calculation can be run, without running it.
+    pub fn validate(&self) -> Result<(), SimulationError> {
+        if self.ranges.is_empty() {
+            return Err(SimulationError::NoPlayers);
+        }
+        if self.board.len() > self.game.board_size() {
+            return Err(SimulationError::TooManyBoardCards{allowed: self.game.board_size(), found: self.board.len()});
+        }
+        if self.num_threads <= 0 {
+            return Err(SimulationError::NoThreads);
+        }
+        if self.num_sims <= 0 {
+            return Err(SimulationError::NoSimulations);
+        }
+        if self.precision.is_some_and(|precision| !precision.is_valid()) {
+            return Err(SimulationError::InvalidPrecision);
+        }
+        let num_draws: usize = (0..self.num_players()).map(|player| self.discard_of(player).max_draws()).sum();
+        if self.game.board_size() + self.dead_cards.len() + self.game.num_hole_cards() * self.num_players() + num_draws > self.rules.deck_size() {
+            return Err(SimulationError::NotEnoughCards{num_players: self.num_players()});
+        }
+        self.check_discards()?;
+        for (player, range) in self.ranges.iter().enumerate() {
+            let expected = self.game.num_hole_cards();
+            let wrong_size = |combo: &&WeightedCombo| if self.game.deals_hole_cards() {
+                combo.cards.len() > expected
+            } else {
+                combo.cards.len() != expected
+            };
+            if let Some(combo) = range.combos().iter().find(wrong_size) {
+                return Err(SimulationError::WrongNumberOfHoleCards{player, expected, found: combo.cards.len()});
+            }
+        }
+        self.check_for_duplicates()?;
+        if self.is_exact() && self.known_hole_cards().is_none() {
+            return Err(SimulationError::UnknownHoleCards);
+        }
+        self.live_ranges().map(|_| ())
+    }
+
+    pub fn run(&self) -> Result<SimulationResult, SimulationError> {
+        self.validate()?;
+
+        let exact = self.is_exact();
+        let total_num_sims = self.total_num_sims();
+        let board_ref = Arc::new(self.board.clone());
+        let dead_cards: CardSet = self.dead_cards.iter().cloned().collect::<CardSet>().union(&self.cards_not_in_deck());
+        let ranges_ref = Arc::new(self.live_ranges()?);
+        let discards_ref = Arc::new((0..self.num_players()).map(|player| self.discard_of(player)).collect::<Vec<Discard>>());
+
+        let sims_per_merge = if self.stops_early() { SIMS_PER_MERGE } else { cmp::max(total_num_sims, 1) };
+        let stopping = StoppingRule{precision: self.precision, deadline: self.time_limit.map(|time_limit| Instant::now() + time_limit)};
+        let empty_result = SimulationResult{
+            num_sims: 0,
+            num_players: self.num_players(),
+            num_random_opponents: self.num_random_opponents,
+            exact,
+            hi_lo: self.hi_lo,
+            outcomes: HashMap::new(),
+            split_outcomes: HashMap::new(),
+        };
+        let merger = Arc::new(Merger{result: Mutex::new(empty_result), stopping, sims_per_merge, stopped: AtomicBool::new(false)});
+        let mut children = Vec::with_capacity(self.num_threads as usize);
+        for thread_index in 0..self.num_threads {
+            let this_num_sims = get_num_sims_for_thread(total_num_sims, self.num_threads, thread_index);
+            let num_threads = self.num_threads;
+            let rng = thread_rng_for(self.seed, thread_index);
+            let this_board_ref = board_ref.clone();
+            let this_ranges_ref = ranges_ref.clone();
+            let this_discards_ref = discards_ref.clone();
+            let this_merger = merger.clone();
+            let showdown = Showdown{game: self.game, rules: self.rules, hi_lo: self.hi_lo};
+            let child_thread = thread::spawn(move || {
+                if exact {
+                    let all_hole_cards: Vec<Card> = this_ranges_ref.iter().flat_map(|range| range.combos()[0].cards.clone()).collect();
+                    enumerate_hands(thread_index, num_threads, showdown, &this_board_ref, &dead_cards, &all_hole_cards, &this_merger)
+                } else {
+                    simulate_hands(this_num_sims, showdown, &this_board_ref, &dead_cards, &this_ranges_ref, &this_discards_ref, &this_merger, rng)
+                }
+            });
+            children.push(child_thread);
+        }
+        for child_thread in children {
+            match child_thread.join() {
+                Ok(thread_result) => thread_result?,
+                Err(e) => panic!("Worker thread died! {:?}", e)
+            }
+        }
+
+        match Arc::try_unwrap(merger) {
+            Ok(merger) => Ok(merger.result.into_inner().unwrap()),
+            Err(_) => panic!("Outcomes are still shared after all threads finished"),
+        }
+    }
+
+    // Makes sure no card is on the board, dead or in someone's known hole cards more than once, or is missing from the deck.
+    fn check_for_duplicates(&self) -> Result<(), SimulationError> {
+        let mut seen_cards = CardSet::empty();
+        let known_cards = self.ranges.iter().
+            filter(|range| range.num_combos() == 1).
+            flat_map(|range| &range.combos()[0].cards);
+        for &card in self.board.iter().chain(&self.dead_cards).chain(known_cards) {
+            if !seen_cards.insert(card) {
+                return Err(SimulationError::DuplicateCard(card));
+            }
+            if !self.rules.in_deck(card) {
+                return Err(SimulationError::NotInDeck(card));
+            }
+        }
+        Ok(())
+    }
+
+    // What the player throws away, if the game has a draw.
+    fn discard_of(&self, player: usize) -> Discard {
+        if self.game != Game::FiveCardDraw {
+            return Discard::StandPat;
+        }
+        self.discards.get(player).cloned().unwrap_or(Discard::Auto)
+    }
+
+    // Makes sure discards are only given in draw, and that discarded cards are in the player's known hand.
+    fn check_discards(&self) -> Result<(), SimulationError> {
+        if self.discards.is_empty() {
+            return Ok(());
+        }
+        if self.game != Game::FiveCardDraw {
+            return Err(SimulationError::NotADrawGame);
+        }
+        for (player, discard) in self.discards.iter().enumerate() {
+            let discarded = match *discard {
+                Discard::Cards(ref cards) => cards,
+                _ => continue,
+            };
+            let range = match self.ranges.get(player) {
+                Some(range) if range.num_combos() == 1 => range,
+                _ => return Err(SimulationError::InvalidDiscard{player}),
+            };
+            let hand: CardSet = range.combos()[0].cards.iter().cloned().collect();
+            let discarded_set: CardSet = discarded.iter().cloned().collect();
+            if discarded_set.len() != discarded.len() || !discarded.iter().all(|&card| hand.contains(card)) {
+                return Err(SimulationError::InvalidDiscard{player});
+            }
+        }
+        Ok(())
+    }
+
+    // The cards the rules leave out of the deck, like the twos through fives in short deck.
+    fn cards_not_in_deck(&self) -> CardSet {
+        CardSet::full().difference(&self.rules.deck())
+    }
+
+    // Each player's range, minus the combos which clash with the board, the dead cards or someone else's known hole cards, or which aren't in the deck.
+    fn live_ranges(&self) -> Result<Vec<HandRange>, SimulationError> {
+        let out_of_play: CardSet = self.board.iter().chain(&self.dead_cards).cloned().collect::<CardSet>().union(&self.cards_not_in_deck());
+        let live_ranges: Vec<HandRange> = self.ranges.iter().enumerate().map(|(player, range)| {
+            let mut dead_cards = out_of_play;
+            for (other_player, other_range) in self.ranges.iter().enumerate() {
+                if other_player != player && other_range.num_combos() == 1 {
+                    dead_cards.extend(&other_range.combos()[0].cards);
+                }
+            }
+            range.without(&dead_cards)
+        }).collect();
+        match live_ranges.iter().position(HandRange::is_empty) {
+            Some(player) => Err(SimulationError::EmptyRange{player}),
+            None => Ok(live_ranges),
+        }
+    }
+}
+
+pub struct SimulationResult {
+    // How many hands were actually simulated, which can be fewer than asked for when stopping early.
+    pub num_sims: i32,
+    // Including the random opponents, who are the last num_random_opponents players.
+    pub num_players: usize,
+    pub num_random_opponents: usize,
+    // Whether every possible board was dealt, making the counts exact.
+    pub exact: bool,
+    pub hi_lo: bool,
+    // Keyed by the indices of the players who won (more than one means a chop). In hi-lo, these are the high hands.
+    pub outcomes: HashMap<Vec<i32>, HandStats>,
+    // Only in hi-lo: how often each set of high winners went with each set of low winners. No low winners means nobody had a low.
+    pub split_outcomes: HashMap<(Vec<i32>, Vec<i32>), i32>,
+}
+
+impl SimulationResult {
+    // All outcomes, most frequent first.
+    pub fn sorted_outcomes(&self) -> Vec<(&Vec<i32>, &HandStats)> {
+        let mut outcomes: Vec<(&Vec<i32>, &HandStats)> = self.outcomes.iter().collect();
+        // Switch the order to get greatest-first, with ties in order of the winners.
+        outcomes.sort_by_key(|&(winners, stats)| (-stats.total_events(), winners));
+        outcomes
+    }
+
+    // How often each set of players won the low half, most frequent first. No winners means nobody had a low.
+    pub fn sorted_low_outcomes(&self) -> Vec<(Vec<i32>, i32)> {
+        let mut low_outcomes: HashMap<Vec<i32>, i32> = HashMap::new();
+        for ((_, low_winners), &num_events) in &self.split_outcomes {
+            *low_outcomes.entry(low_winners.clone()).or_insert(0) += num_events;
+        }
+        let mut low_outcomes: Vec<(Vec<i32>, i32)> = low_outcomes.into_iter().collect();
+        low_outcomes.sort_by(|&(ref winners, num_events), &(ref other_winners, other_num_events)|
+            (-num_events, winners).cmp(&(-other_num_events, other_winners)));
+        low_outcomes
+    }
+
+    // How each player did overall, indexed the same as the hole cards.
+    pub fn player_equities(&self) -> Vec<PlayerEquity> {
+        (0..self.num_players as i32).map(|player| self.equity_of(|other| other == player)).collect()
+    }
+
+    // How the random opponents did between them. A chop among only random opponents counts as a win for the field.
+    pub fn field_equity(&self) -> PlayerEquity {
+        let first_opponent = (self.num_players - self.num_random_opponents) as i32;
+        self.equity_of(|player| player >= first_opponent)
+    }
+
+    // How a group of players, picked out by is_member, did between them.
+    fn equity_of<F: Fn(i32) -> bool>(&self, is_member: F) -> PlayerEquity {
+        let mut equity = PlayerEquity::create();
+        self.for_each_pot(|high_winners, low_winners, num_events| {
+            let high_share = share_of(high_winners, &is_member);
+            let low_share = share_of(low_winners, &is_member);
+            let pot_share = if low_winners.is_empty() { high_share } else { (high_share + low_share) / 2f64 };
+            if pot_share == 0f64 {
+                return;
+            }
+            let whole_pot = high_winners.iter().all(|&winner| is_member(winner)) && low_winners.iter().all(|&winner| is_member(winner));
+            if whole_pot {
+                equity.wins += num_events;
+            } else {
+                equity.ties += num_events;
+            }
+            if high_share > 0f64 {
+                equity.highs += num_events;
+            }
+            if low_share > 0f64 {
+                equity.lows += num_events;
+            }
+            equity.pots_won += num_events as f64 * pot_share;
+            equity.pots_won_squared += num_events as f64 * pot_share * pot_share;
+        });
+        equity
+    }
+
+    /*
+    Calls back with the high winners, the low winners (if there was a low) and
+    how often that happened. It goes in order of the winners, so that adding
+    up shares of pots always rounds the same way.
+    */
+    fn for_each_pot<F: FnMut(&[i32], &[i32], i32)>(&self, mut callback: F) {
+        if self.hi_lo {
+            let mut pots: Vec<_> = self.split_outcomes.iter().collect();
+            pots.sort_by_key(|&(winners, _)| winners);
+            for ((high_winners, low_winners), &num_events) in pots {
+                callback(high_winners, low_winners, num_events);
+            }
+        } else {
+            let mut pots: Vec<(&Vec<i32>, &HandStats)> = self.outcomes.iter().collect();
+            pots.sort_by_key(|&(winners, _)| winners);
+            for (winners, stats) in pots {
+                callback(winners, &[], stats.total_events());
+            }
+        }
+    }
+
+    // A player's or the field's share of the pots, with its standard error.
+    pub fn equity_estimate(&self, equity: &PlayerEquity) -> Estimate {
+        let num_sims = self.num_sims as f64;
+        Estimate::of_mean(equity.pots_won / num_sims, equity.pots_won_squared / num_sims, self.num_sims, self.exact)
+    }
+
+    // The fraction of the simulated hands some number of events is, with its standard error.
+    pub fn frequency_estimate(&self, num_events: i32) -> Estimate {
+        Estimate::of_proportion(num_events, self.num_sims, self.exact)
+    }
+
+    // Whether every player's equity is known as precisely as asked.
+    pub fn is_precise_enough(&self, precision: &Precision) -> bool {
+        self.player_equities().iter().all(|equity| precision.is_met_by(&self.equity_estimate(equity)))
+    }
+
+    // Adds in the hands from a thread's tally.
+    fn merge(&mut self, tally: &Tally) {
+        self.num_sims += tally.num_sims;
+        for (&winners, events) in &tally.outcomes {
+            let merged_stats = self.outcomes.entry(winner_indices(winners)).or_insert_with(HandStats::create);
+            for (merged_events, events) in merged_stats.events.iter_mut().zip(events.iter()) {
+                *merged_events += events;
+            }
+        }
+        for (&(high_winners, low_winners), &num_events) in &tally.split_outcomes {
+            *self.split_outcomes.entry((winner_indices(high_winners), winner_indices(low_winners))).or_insert(0) += num_events;
+        }
+    }
+
+    // What percentage of the simulated hands some number of events is.
+    pub fn percent_of_sims(&self, num_events: f64) -> f64 {
+        (num_events / self.num_sims as f64) * 100f64
+    }
+}
+
+// The fraction of the winners who are picked out by is_member.
+fn share_of<F: Fn(i32) -> bool>(winners: &[i32], is_member: &F) -> f64 {
+    if winners.is_empty() {
+        return 0f64;
+    }
+    winners.iter().filter(|&&winner| is_member(winner)).count() as f64 / winners.len() as f64
+}
+
+pub struct PlayerEquity {
+    // Hands where the whole pot was won outright. In hi-lo, that means scooping.
+    pub wins: i32,
+    // Hands where only part of the pot was won.
+    pub ties: i32,
+    // Hands where all or part of the high half was won. Without hi-lo, that's the whole pot.
+    pub highs: i32,
+    // Hands where all or part of the low half was won.
+    pub lows: i32,
+    // Pots won, where a chop between k players counts as 1/k of a pot, or of half a pot in hi-lo.
+    pub pots_won: f64,
+    // The sum of the square of the share of each pot won, for working out how much the share varies.
+    pub pots_won_squared: f64,
+}
+
+impl PlayerEquity {
+    pub fn create() -> PlayerEquity {
+        PlayerEquity{wins: 0, ties: 0, highs: 0, lows: 0, pots_won: 0f64, pots_won_squared: 0f64}
+    }
+}
+
+pub struct HandStats {
+    pub events: [i32; NUM_HANDS], // Number of times each hand happened
+}
+
+impl HandStats {
+    pub fn create() -> HandStats {
+        HandStats{events: [0; NUM_HANDS]}
+    }
+
+    // Records a hand, given its rank from the evaluator.
+    pub fn add_event(&mut self, hand_rank: u16) -> Result<(), InvalidHandError> {
+        let event_index = evaluator::category(hand_rank)?;
+        self.events[event_index as usize] += 1;
+        Ok(())
+    }
+
+    // Like add_event, for a rank from evaluator::evaluate_with_rules.
+    pub fn add_event_with_rules(&mut self, hand_rank: u16, rules: &Rules) -> Result<(), InvalidHandError> {
+        let event_index = evaluator::category_with_rules(hand_rank, rules)?;
+        self.events[event_index as usize] += 1;
+        Ok(())
+    }
+
+    pub fn total_events(&self) -> i32 {
+        self.events.iter().sum()
+    }
+}
+
+// There's a discard for every player, with the random opponents after the ranges.
+#[allow(clippy::too_many_arguments)] // Everything a thread needs, and it's only called from run().
+fn simulate_hands(num_sims: i32, showdown: Showdown, initial_board: &[Card], dead_cards: &CardSet, ranges: &[HandRange], discards: &[Discard], merger: &Merger, mut rng: XorShiftRng) -> Result<(), SimulationError> {
+    // Known hole cards never change, so they can stay out of the deck for good.
+    let known_hole_cards: Vec<Card> = ranges.iter().
+        filter(|range| range.num_combos() == 1).
+        flat_map(|range| range.combos()[0].cards.clone()).
+        collect();
+    let mut deck = Deck::without(&used_cards(initial_board, dead_cards, &known_hole_cards));
+    let samplers: Vec<ComboSampler> = ranges.iter().map(ComboSampler::create).collect();
+    // Everyone's hole cards, one player after another.
+    let num_hole_cards = showdown.game.num_hole_cards();
+    let num_players = discards.len();
+    let mut all_hole_cards = vec![card(Ace, Spades); num_players * num_hole_cards]; // Dummies
+    let num_range_cards = ranges.len() * num_hole_cards;
+    // How many of each player's hole cards came from their range. The rest are dealt from the deck.
+    let mut nums_from_ranges = vec![0; num_players];
+    let mut board = vec![card(Ace, Spades); showdown.game.board_size()]; // Dummies
+    board[..initial_board.len()].copy_from_slice(initial_board);
+    let mut tally = Tally::create();
+    for sim_index in 0..num_sims {
+        if sim_index > 0 && sim_index % merger.sims_per_merge == 0 {
+            if !merger.merge(&tally) {
+                return Ok(());
+            }
+            tally.clear();
+        }
+        let hole_cards_used = deal_hole_cards(&samplers, &mut all_hole_cards[..num_range_cards], &mut nums_from_ranges[..ranges.len()], &mut rng)?;
+        deck.reset();
+        for (hole_cards, &num_from_range) in all_hole_cards.chunks_mut(num_hole_cards).zip(&nums_from_ranges) {
+            for hole_card in hole_cards[num_from_range..].iter_mut() {
+                *hole_card = deal_unused_card(&mut deck, &hole_cards_used, &mut rng);
+            }
+        }
+        for board_card in board[initial_board.len()..].iter_mut() {
+            *board_card = deal_unused_card(&mut deck, &hole_cards_used, &mut rng);
+        }
+        for (hole_cards, discard) in all_hole_cards.chunks_mut(num_hole_cards).zip(discards) {
+            if let Discard::StandPat = *discard {
+                continue;
+            }
+            let kept = discard.kept(hole_cards, &showdown.rules)?;
+            for (hole_card, &keep) in hole_cards.iter_mut().zip(&kept) {
+                if !keep {
+                    *hole_card = deal_unused_card(&mut deck, &hole_cards_used, &mut rng);
+                }
+            }
+        }
+        showdown.record(&board, &all_hole_cards, &mut tally)?;
+    }
+    merger.merge(&tally);
+    Ok(())
+}
+
+/*
+Picks hole cards for everyone from their ranges, making sure no card is used
+twice. A combo can be smaller than a player's hand in stud, so how many
+cards each player got is filled into nums_dealt. Returns all the cards used.
+*/
+fn deal_hole_cards<R: Rng>(samplers: &[ComboSampler], all_hole_cards: &mut [Card], nums_dealt: &mut [usize], rng: &mut R) -> Result<CardSet, SimulationError> {
+    let num_hole_cards = all_hole_cards.len() / samplers.len();
+    /*
+    If some cards clash, start over with everyone. Redealing just the player
+    who clashed would favour the combos that don't clash with whoever was
+    dealt first.
+    */
+    'attempts: for _ in 0..MAX_DEALING_ATTEMPTS {
+        let mut used_cards = CardSet::empty();
+        for ((hole_cards, num_dealt), sampler) in all_hole_cards.chunks_mut(num_hole_cards).zip(nums_dealt.iter_mut()).zip(samplers) {
+            let combo = sampler.sample(rng);
+            if !combo.iter().all(|&card| used_cards.insert(card)) {
+                continue 'attempts;
+            }
+            hole_cards[..combo.len()].copy_from_slice(combo);
+            *num_dealt = combo.len();
+        }
+        return Ok(used_cards);
+    }
+    Err(SimulationError::RangesClash)
+}
+
+// Deals every board completion whose position in the enumeration belongs to this thread.
+fn enumerate_hands(thread_index: i32, num_threads: i32, showdown: Showdown, initial_board: &[Card], dead_cards: &CardSet, all_hole_cards: &[Card], merger: &Merger) -> Result<(), SimulationError> {
+    let unseen_cards: Vec<Card> = CardSet::full().difference(&used_cards(initial_board, dead_cards, all_hole_cards)).iter().collect();
+
+    let mut board = vec![card(Ace, Spades); showdown.game.board_size()]; // Dummies
+    board[..initial_board.len()].copy_from_slice(initial_board);
+    let num_missing = board.len() - initial_board.len();
+
+    let mut tally = Tally::create();
+    let mut completion_index = 0;
+    // The first error stops any more hands being recorded.
+    let mut recorded = Ok(());
+    for_each_combination(&unseen_cards, num_missing, |completion| {
+        if recorded.is_ok() && completion_index % num_threads == thread_index {
+            board[initial_board.len()..].copy_from_slice(completion);
+            recorded = showdown.record(&board, all_hole_cards, &mut tally);
+        }
+        completion_index += 1;
+    });
+    recorded?;
+    merger.merge(&tally);
+    Ok(())
+}
+
+/*
+A set of players, as a bit per player index. There can never be more than
+26 players, since each gets at least two cards from a 52-card deck.
+*/
+type WinnerMask = u64;
+
+// The indices of the players in a set, in order.
+fn winner_indices(winners: WinnerMask) -> Vec<i32> {
+    (0..64).filter(|&index| winners & (1 << index) != 0).collect()
+}
+
+/*
+Hashes winner masks with a single multiply, instead of the default hasher's
+much slower one, which is there to resist collisions picked by an attacker.
+*/
+#[derive(Default)]
+struct MaskHasher(u64);
+
+impl Hasher for MaskHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.write_u64(u64::from(byte));
+        }
+    }
+
+    fn write_u64(&mut self, word: u64) {
+        self.0 = (self.0.rotate_left(29) ^ word).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+type MaskMap<K, V> = HashMap<K, V, BuildHasherDefault<MaskHasher>>;
+
+/*
+What one thread has recorded about the hands it's played since it last
+merged. It's only ever touched by its own thread, and it's keyed by winner
+masks so recording a hand doesn't allocate, which would make the threads
+wait on the allocator instead.
+*/
+struct Tally {
+    num_sims: i32,
+    // How often each type of hand won, per set of winners.
+    outcomes: MaskMap<WinnerMask, [i32; NUM_HANDS]>,
+    // Only in hi-lo: how often each set of high winners went with each set of low winners.
+    split_outcomes: MaskMap<(WinnerMask, WinnerMask), i32>,
+}
+
+impl Tally {
+    fn create() -> Tally {
+        Tally{num_sims: 0, outcomes: MaskMap::default(), split_outcomes: MaskMap::default()}
+    }
+
+    // Empties the tally, keeping the maps' space for reuse.
+    fn clear(&mut self) {
+        self.num_sims = 0;
+        self.outcomes.clear();
+        self.split_outcomes.clear();
+    }
+}
+
+// Gathers up the threads' tallies, and tells them when to stop.
+struct Merger {
+    result: Mutex<SimulationResult>,
+    stopping: StoppingRule,
+    // Threads merge after this many hands, and once more when they're done.
+    sims_per_merge: i32,
+    stopped: AtomicBool,
+}
+
+impl Merger {
+    // Returns whether the threads should keep going.
+    fn merge(&self, tally: &Tally) -> bool {
+        let mut result = self.result.lock().unwrap();
+        result.merge(tally);
+        if self.stopping.is_met_by(&result) {
+            self.stopped.store(true, Ordering::Relaxed);
+        }
+        !self.stopped.load(Ordering::Relaxed)
+    }
+}
+
+// When to stop simulating before the planned number of hands.
+struct StoppingRule {
+    precision: Option<Precision>,
+    deadline: Option<Instant>,
+}
+
+impl StoppingRule {
+    fn is_met_by(&self, result: &SimulationResult) -> bool {
+        if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return true;
+        }
+        match self.precision {
+            Some(ref precision) => result.num_sims >= MIN_SIMS_FOR_PRECISION && result.is_precise_enough(precision),
+            None => false,
+        }
+    }
+}
+
+// How the players' hands are compared at the end of each hand.
+#[derive(Copy, Clone)]
+struct Showdown {
+    game: Game,
+    rules: Rules,
+    hi_lo: bool,
+}
+
+impl Showdown {
+    // Evaluates everyone's hand on a complete board and records who won. The hole cards are one player's after another.
+    fn record(&self, board: &[Card], all_hole_cards: &[Card], tally: &mut Tally) -> Result<(), SimulationError> {
+        let num_hole_cards = self.game.num_hole_cards();
+        let mut error = None;
+        // Lower ranks are better hands, so reverse them to find the winners.
+        let (winners, best_rank) = find_winners(all_hole_cards.chunks(num_hole_cards).
+            map(|hole_cards| match self.game.evaluate(hole_cards, board, &self.rules) {
+                Ok(rank) => Some(Reverse(rank)),
+                Err(evaluation_error) => {
+                    error = Some(evaluation_error);
+                    None
+                },
+            }));
+        if let Some(error) = error {
+            return Err(error.into());
+        }
+        let Reverse(best_rank) = best_rank.ok_or(SimulationError::NoPlayers)?;
+        let low_winners = if self.hi_lo {
+            let (low_winners, _) = find_winners(all_hole_cards.chunks(num_hole_cards).
+                map(|hole_cards| self.game.evaluate_low(hole_cards, board)));
+            Some(low_winners)
+        } else {
+            None
+        };
+
+        tally.num_sims += 1;
+        let category = evaluator::category_with_rules(best_rank, &self.rules)?;
+        tally.outcomes.entry(winners).or_insert([0; NUM_HANDS])[category as usize] += 1;
+        if let Some(low_winners) = low_winners {
+            *tally.split_outcomes.entry((winners, low_winners)).or_insert(0) += 1;
+        }
+        Ok(())
+    }
+}
+
+// The players with the best hand, and that hand. Players with no hand never win.
+fn find_winners<T: Ord, I: Iterator<Item=Option<T>>>(hands: I) -> (WinnerMask, Option<T>) {
+    let mut winners = 0;
+    let mut best_hand = None;
+    for (index, hand) in hands.enumerate() {
+        if hand.is_none() {
+            continue;
+        }
+        if hand == best_hand {
+            winners |= 1 << index;
+        } else if hand > best_hand {
+            winners = 1 << index;
+            best_hand = hand;
+        }
+    }
+    (winners, best_hand)
+}
+
+// The random numbers for one thread. Seeded ones start from the seed plus the thread's index.
+fn thread_rng_for(seed: Option<u64>, thread_index: i32) -> XorShiftRng {
+    match seed {
+        Some(seed) => XorShiftRng::from_seed(expand_seed(seed.wrapping_add(thread_index as u64))),
+        None => rand::weak_rng(),
+    }
+}
+
+/*
+Spreads a seed over the four words XorShiftRng starts from, using two steps
+of SplitMix64. That makes nearby seeds, like one thread's and the next's,
+give unrelated numbers, and since each step is a bijection of a different
+state, the words are never all zero, which XorShiftRng can't start from.
+*/
+fn expand_seed(seed: u64) -> [u32; 4] {
+    let mut state = seed;
+    let mut next_word = || {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    };
+    let first = next_word();
+    let second = next_word();
+    [first as u32, (first >> 32) as u32, second as u32, (second >> 32) as u32]
+}
+
+// Deals a random card from the deck that isn't in use. The deck never deals the same card twice until it's reset.
+fn deal_unused_card<R: Rng>(deck: &mut Deck, used_cards: &CardSet, rng: &mut R) -> Card {
+    loop {
+        let card = deck.deal_random(rng).expect("Ran out of cards to deal");
+        if !used_cards.contains(card) {
+            return card;
+        }
+    }
+}
+
+// All the cards that are already out of the deck.
+fn used_cards(initial_board: &[Card], dead_cards: &CardSet, all_hole_cards: &[Card]) -> CardSet {
+    let mut used_cards = initial_board.iter().cloned().collect::<CardSet>().union(dead_cards);
+    used_cards.extend(all_hole_cards);
+    used_cards
+}
+
+// Calls back with every way of choosing num_chosen of the given cards, in a fixed order.
+fn for_each_combination<F: FnMut(&[Card])>(cards: &[Card], num_chosen: usize, mut callback: F) {
+    if num_chosen > cards.len() {
+        return;
+    }
+    // Indices into cards, always strictly increasing.
+    let mut indices: Vec<usize> = (0..num_chosen).collect();
+    let mut chosen: Vec<Card> = indices.iter().map(|&index| cards[index]).collect();
+    loop {
+        callback(&chosen);
+
+        // Find the rightmost index that can still move right, like incrementing an odometer.
+        let mut position = num_chosen;
+        loop {
+            if position == 0 {
+                return;
+            }
+            position -= 1;
+            if indices[position] < cards.len() - num_chosen + position {
+                break;
+            }
+        }
+        indices[position] += 1;
+        for later in (position + 1)..num_chosen {
+            indices[later] = indices[later - 1] + 1;
+        }
+        for later in position..num_chosen {
+            chosen[later] = cards[indices[later]];
+        }
+    }
+}
+
+fn num_combinations(num_items: usize, num_chosen: usize) -> u64 {
+    if num_chosen > num_items {
+        return 0;
+    }
+    let mut combinations: u64 = 1;
+    for index in 0..num_chosen {
+        // Stays integral at every step since it's always a smaller binomial coefficient.
+        combinations = combinations * (num_items - index) as u64 / (index as u64 + 1);
+    }
+    combinations
+}
+
+fn get_num_sims_for_thread(total_num_sims: i32, total_num_threads: i32, thread_index: i32) -> i32 {
+    assert!(total_num_threads > thread_index);
+    let base_num_sims = total_num_sims / total_num_threads;
+    let threads_with_extra = total_num_sims % total_num_threads;
+    let this_threads_extra =
+        if thread_index < threads_with_extra {
+            1
+        } else {
+            0
+        };
+    base_num_sims + this_threads_extra
+}
@@ -1,336 +1,329 @@
-extern crate rand;
 extern crate getopts;
 extern crate num_cpus;
 extern crate cards;
 extern crate poker_hands;
+extern crate poker_odds;
+
+mod output;
+mod output_tests;
 
 use std::env;
-use std::collections::HashMap;
+use std::process;
 use std::str::FromStr;
-use std::thread;
-use std::sync::*;
+use std::time::Duration;
 use getopts::{Options, Matches, HasArg, Occur};
-use rand::{thread_rng, Rng};
 
-use cards::{Card, Rank, Suit, card};
-use cards::Rank::*;
-use cards::Suit::*;
-use poker_hands::{Hand, NUM_HANDS};
+use cards::{Card, parse_cards};
+use poker_hands::Rules;
+use poker_odds::{Discard, EquityCalculator, DEFAULT_CONFIDENCE, Game, HandRange, Precision, SimulationMode, BOARD_SIZE};
+use output::{Format, cards_to_string};
 
 fn main() {
+    if let Err(message) = run() {
+        eprintln!("Error: {}", message);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), String> {
     let args: Vec<String> = env::args().collect();
     let opts = create_opts();
     let arg_matches = match opts.parse(&args[1..]) {
         Ok(matches) => matches,
-        Err(error) => panic!("Could not parse {:?}; error: {:?}", args, error)
-    };
-
-    let initial_board = get_initial_board(&arg_matches);
-    let total_num_sims =
-    if initial_board.len() == BOARD_SIZE {
-        println!("The given board is full, so there's no uncertainty.");
-        1
-    } else {
-        get_num_sims(&arg_matches)
+        Err(error) => return Err(error.to_string())
     };
-    let all_hole_cards = get_hole_cards(&arg_matches);
-    let num_threads = get_num_threads(&arg_matches);
+    if arg_matches.opt_present(HELP_ARG) {
+        print!("{}", opts.usage(&format!("Usage: {} -h HAND -h HAND [options]", args[0])));
+        return Ok(());
+    }
 
-    println!("Simulating {} hands", total_num_sims);
-    if initial_board.len() > 0 {
-        println!("For board {:?}", initial_board);
+    let initial_board = get_initial_board(&arg_matches)?;
+    let (mut hand_names, ranges) = get_ranges(&arg_matches)?;
+    let mut calculator = EquityCalculator::create_with_ranges(ranges, initial_board, get_num_sims(&arg_matches)?);
+    calculator.game = get_game(&arg_matches)?;
+    calculator.rules = get_rules(&arg_matches)?;
+    calculator.hi_lo = arg_matches.opt_present(HI_LO_ARG);
+    calculator.dead_cards = get_dead_cards(&arg_matches)?;
+    calculator.num_random_opponents = get_num_random_opponents(&arg_matches)?;
+    calculator.discards = get_discards(&arg_matches)?;
+    hand_names.extend((0..calculator.num_random_opponents).map(|_| "(random)".to_string()));
+    calculator.num_threads = get_num_threads(&arg_matches)?;
+    calculator.mode = get_mode(&arg_matches)?;
+    calculator.validate().map_err(|error| error.to_string())?;
+    let format = get_format(&arg_matches)?;
+    let confidence = get_confidence(&arg_matches)?;
+    calculator.precision = get_precision(&arg_matches, confidence)?;
+    calculator.time_limit = get_time_limit(&arg_matches)?;
+    calculator.seed = get_seed(&arg_matches)?;
+    if !arg_matches.opt_present(NUM_SIMS_ARG) && (calculator.precision.is_some() || calculator.time_limit.is_some()) {
+        // Leave it to the precision or time limit to say when to stop.
+        calculator.num_sims = i32::MAX;
     }
-    println!("Using {} threads", num_threads);
-    let board_ref = Arc::new(initial_board);
-    let hole_cards_ref = Arc::new(all_hole_cards);
 
-    let outcomes = Arc::new(Mutex::new(HashMap::new()));
-    let mut children = Vec::with_capacity(num_threads as usize);
-    for thread_index in 0..num_threads {
-        let this_num_sims = get_num_sims_for_thread(total_num_sims, num_threads as i32, thread_index as i32);
-        let this_board_ref = board_ref.clone();
-        let this_hole_cards_ref = hole_cards_ref.clone();
-        let this_outcomes = outcomes.clone();
-        let child_thread = thread::spawn(move || {
-                simulate_hands(this_num_sims, &this_board_ref, &this_hole_cards_ref, &this_outcomes)
-        });
-        children.push(child_thread);
+    if format == Format::Text {
+        print_text_header(&calculator);
     }
-    for child_thread in children {
-        match child_thread.join() {
-            Ok(_) => continue,
-            Err(e) => panic!("Worker thread died! {:?}", e)
-        }
+    let result = calculator.run().map_err(|error| error.to_string())?;
+    if format == Format::Text && calculator.stops_early() {
+        println!("Stopped after {} hands", result.num_sims);
     }
-
-    let final_outcomes = outcomes.lock().unwrap();
-
-    let sorted_outcomes = sort_descending(
-        final_outcomes.iter().map(|(outcome, stats)| (outcome.clone(), stats.total_events())).collect());
-
-    for outcome in sorted_outcomes {
-        let stats = final_outcomes.get(&outcome).unwrap();
-        let total_events = stats.total_events();
-        let outcome_percent = (total_events as f64 / total_num_sims as f64) * 100f64;
-        let outcome_name = name_outcome(&outcome, &hole_cards_ref);
-        println!("{} ({} times, {}%)", outcome_name, total_events, outcome_percent);
-        let sorted_hand_indices = sort_descending(
-            (0..NUM_HANDS).map(|index| (index, stats.events[index])).collect());
-        for hand_index in sorted_hand_indices {
-            let hand_events = stats.events[hand_index];
-            if hand_events == 0 {
-                continue;
-            }
-            let hand_percent = (hand_events as f64 / total_events as f64) * 100f64;
-            println!("\t{}: {} times, {}%", Hand::name_hand_index(hand_index), hand_events, hand_percent);
-        }
+    match format {
+        Format::Text => output::print_text(&result, &hand_names, confidence),
+        Format::Json => println!("{}", output::to_json(&calculator, &result, &hand_names, confidence)),
+        Format::Csv => print!("{}", output::to_csv(&result, &hand_names, confidence)),
     }
+    Ok(())
 }
 
-fn simulate_hands(num_sims: i32, initial_board: &[Card], all_hole_cards: &[[Card; 2]], outcomes: &Mutex<HashMap<Vec<i32>, HandStats>>) {
-    for _ in 0..num_sims {
-        let board = pick_random_board(initial_board, all_hole_cards);
-        assert!(board.len() == BOARD_SIZE);
-        let mut hands = Vec::with_capacity(all_hole_cards.len());
-        for hole_cards in all_hole_cards {
-            let mut cards: Vec<Card> = Vec::with_capacity(hole_cards.len() + board.len());
-            cards.extend(board.iter().cloned());
-            cards.extend(hole_cards.iter().cloned());
-            // Sort descending - best_hand_of() requires this.
-            cards.sort_by(|first, second| second.cmp(first));
-            let hand = Hand::best_hand_of(&cards);
-            hands.push(hand);
+fn print_text_header(calculator: &EquityCalculator) {
+    if calculator.board.len() == calculator.game.board_size() && calculator.known_hole_cards().is_some() {
+        println!("The given board is full, so there's no uncertainty.");
+    }
+    if calculator.is_exact() {
+        println!("Enumerating all {} boards", calculator.total_num_sims());
+    } else if calculator.stops_early() {
+        if let Some(precision) = calculator.precision {
+            println!("Simulating until every equity is within {}% with {}% confidence", precision.margin * 100f64, precision.confidence * 100f64);
         }
-        assert!(hands.len() == all_hole_cards.len());
-
-        let mut winners = Vec::new();
-        winners.push(0);
-        let mut best_hand = hands[0];
-        for index in 1..hands.len() {
-            let hand = hands[index];
-            if hand == best_hand {
-                winners.push(index as i32);
-            } else if hand > best_hand {
-                winners.clear();
-                winners.push(index as i32);
-                best_hand = hand;
-            }
+        if let Some(time_limit) = calculator.time_limit {
+            println!("Simulating for at most {} seconds", duration_seconds(time_limit));
+        }
+        if calculator.num_sims < i32::MAX {
+            println!("Simulating at most {} hands", calculator.num_sims);
         }
-        insert_outcome(&mut outcomes.lock().unwrap(), &winners, &best_hand);
+    } else {
+        println!("Simulating {} hands", calculator.total_num_sims());
+    }
+    if !calculator.board.is_empty() {
+        println!("For board {}", cards_to_string(&calculator.board));
+    }
+    if !calculator.dead_cards.is_empty() {
+        println!("With dead cards {}", cards_to_string(&calculator.dead_cards));
+    }
+    println!("Using {} threads", calculator.num_threads);
+    if let Some(seed) = calculator.seed {
+        println!("Seeded with {}", seed);
     }
 }
 
-fn sort_descending<T: Clone>(mut items: Vec<(T, i32)>) -> Vec<T> {
-    // Switch the order to get greatest-first.
-    items.sort_by(|&(_, first), &(_, second)| second.cmp(&first));
-    items.iter().map(|&(ref item, _)| item.clone()).collect()
-}
-
-const HOLE_CARDS_ARG: &'static str = "h";
-const NUM_SIMS_ARG: &'static str = "n";
-const NUM_THREADS_ARG: &'static str = "t";
-const BOARD_ARG: &'static str = "b";
+const HOLE_CARDS_ARG: &str = "h";
+const NUM_SIMS_ARG: &str = "n";
+const NUM_THREADS_ARG: &str = "t";
+const BOARD_ARG: &str = "b";
+const MODE_ARG: &str = "m";
+const RANDOM_OPPONENTS_ARG: &str = "o";
+const DEAD_CARDS_ARG: &str = "d";
+const GAME_ARG: &str = "g";
+const HI_LO_ARG: &str = "l";
+const RULES_ARG: &str = "r";
+const DISCARD_ARG: &str = "x";
+const FORMAT_ARG: &str = "f";
+const CONFIDENCE_ARG: &str = "c";
+const PRECISION_ARG: &str = "p";
+const TIME_LIMIT_ARG: &str = "s";
+// Only the long --seed, since -s is taken.
+const SEED_ARG: &str = "seed";
+// Only the long --help, since -h is the hole cards.
+const HELP_ARG: &str = "help";
 fn create_opts() -> Options {
     // Unfortunately, there doesn't seem to be a way to require that an option appears at least once.
     let mut opts = Options::new();
-    opts.opt(HOLE_CARDS_ARG, "hole cards", "A single player's hole cards", "XxYy", HasArg::Yes, Occur::Multi);
+    opts.opt(HOLE_CARDS_ARG, "hole cards", "A single player's hole cards, or a range of them like \"QQ+,AKs\". In stud, the cards seen so far.", "XxYy", HasArg::Yes, Occur::Multi);
     opts.opt(NUM_SIMS_ARG, "number of simulations", "The number of hands to simulate in order to approximate the true distribution.", "n", HasArg::Yes, Occur::Optional);
     opts.opt(NUM_THREADS_ARG, "number of threads to use", "The number of threads to use simultaneously to run the simulations.", "t", HasArg::Yes, Occur::Optional);
     opts.opt(BOARD_ARG, "board cards", "The cards already on the board.", "XxYyZz", HasArg::Yes, Occur::Optional);
+    opts.opt(MODE_ARG, "simulation mode", "Whether to enumerate every possible board (exact) or simulate random ones (random). By default, boards are enumerated when there are few enough of them.", "exact|random", HasArg::Yes, Occur::Optional);
+    opts.opt(DEAD_CARDS_ARG, "dead", "Cards known to be out of play, like folded or exposed cards.", "XxYy", HasArg::Yes, Occur::Optional);
+    opts.opt(GAME_ARG, "game", "Which game to play: hold'em (the default), Omaha with 4, 5 or 6 hole cards, seven-card stud or five-card draw.", "holdem|omaha|omaha5|omaha6|stud|draw", HasArg::Yes, Occur::Optional);
+    opts.opt(RULES_ARG, "rules", "Which deck and hand rankings to use: standard (the default), or short deck (six-plus), where a flush beats a full house.", "standard|shortdeck", HasArg::Yes, Occur::Optional);
+    opts.opt(DISCARD_ARG, "discard", "In draw, the cards a player throws away, given once per player in the same order as the hole cards. \"auto\" picks them with a simple strategy, which is also what players without one use, and \"none\" stands pat.", "XxYy|auto|none", HasArg::Yes, Occur::Multi);
+    opts.opt(FORMAT_ARG, "format", "How to write the results: as text (the default), or as JSON or CSV for other programs. \
+        JSON is one object with schema_version, inputs (the options given), num_sims, exact, players, field (the random opponents together, or null), outcomes and low_outcomes. \
+        Each player, and the field, has index, hand, random, equity (from 0 to 1), wins, ties, highs, lows, standard_error and confidence_interval. \
+        Each outcome has winners (player indices), count, hands (how often each type of hand won), frequency, standard_error and confidence_interval. \
+        Fields are only ever added, never changed or removed. \
+        CSV has a header and a row per player, plus one for the field: index,hand,random,equity,wins,ties,highs,lows,num_sims,standard_error,ci_low,ci_high.", "text|json|csv", HasArg::Yes, Occur::Optional);
+    opts.opt(CONFIDENCE_ARG, "confidence", "How sure the margins of error on simulated results should be, between 0 and 1. The default is 0.95.", "p", HasArg::Yes, Occur::Optional);
+    opts.opt(PRECISION_ARG, "precision", "Instead of simulating a set number of hands, stop once every equity is known to within this many percent either side, at the confidence given by -c. Any -n is then the most hands to simulate. Only applies to random simulation, since enumerated boards give exact equities.", "percent", HasArg::Yes, Occur::Optional);
+    opts.opt(TIME_LIMIT_ARG, "seconds", "Stop simulating after about this many seconds. Any -n is then the most hands to simulate. Only applies to random simulation, like -p.", "seconds", HasArg::Yes, Occur::Optional);
+    opts.opt("", SEED_ARG, "Seeds the random numbers, so that running again with the same options and number of threads gives the same results. Not when stopping at a time limit, or at a precision with more than one thread.", "n", HasArg::Yes, Occur::Optional);
+    opts.optflag("", HELP_ARG, "Prints this help.");
+    opts.optflag(HI_LO_ARG, "hilo", "Split each pot between the best high hand and the best eight-or-better low.");
+    opts.opt(RANDOM_OPPONENTS_ARG, "number of random opponents", "The number of extra players whose hole cards are dealt at random.", "n", HasArg::Yes, Occur::Optional);
     opts
 }
 
-fn get_initial_board(matches: &Matches) -> Vec<Card> {
+fn get_initial_board(matches: &Matches) -> Result<Vec<Card>, String> {
     if !matches.opt_present(BOARD_ARG) {
-        return Vec::new();
+        return Ok(Vec::new());
+    }
+    let board_string = matches.opt_str(BOARD_ARG).unwrap();
+    let initial_board = parse_cards_string(&board_string)?;
+    if initial_board.len() > BOARD_SIZE {
+        return Err(format!("The board can have at most {} cards: {}", BOARD_SIZE, board_string));
+    }
+    Ok(initial_board)
+}
+
+fn get_dead_cards(matches: &Matches) -> Result<Vec<Card>, String> {
+    match matches.opt_str(DEAD_CARDS_ARG) {
+        Some(dead_string) => parse_cards_string(&dead_string),
+        None => Ok(Vec::new()),
     }
-    let board_string = matches.opt_str(&BOARD_ARG).unwrap();
-    let initial_board = parse_cards_string(&board_string);
-    assert!(initial_board.len() <= BOARD_SIZE, "Initial board has more than {} cards! {}", BOARD_SIZE, board_string);
-    initial_board
 }
 
-fn get_hole_cards(matches: &Matches) -> Vec<[Card; 2]> {
-    assert!(matches.opt_count(HOLE_CARDS_ARG) >= 1, "No hole cards specified");
+// Returns each player's range along with how it was written, for naming it later.
+fn get_ranges(matches: &Matches) -> Result<(Vec<String>, Vec<HandRange>), String> {
+    if matches.opt_count(HOLE_CARDS_ARG) == 0 {
+        return Err("No hole cards specified".to_string());
+    }
     let hole_strings = matches.opt_strs(HOLE_CARDS_ARG);
-    let mut all_hole_cards = Vec::with_capacity(hole_strings.len());
+    let mut ranges = Vec::with_capacity(hole_strings.len());
     for hole_string in &hole_strings {
-        let hole_cards = parse_cards_string(hole_string);
-        assert!(hole_cards.len() == 2, "{} specifies {} cards, not 2", hole_string, hole_cards.len());
-        all_hole_cards.push([hole_cards[0], hole_cards[1]]);
+        let range: HandRange = match hole_string.parse() {
+            Ok(range) => range,
+            Err(error) => return Err(format!("Couldn't parse {} as hole cards: {}", hole_string, error))
+        };
+        if range.is_empty() {
+            return Err(format!("{} doesn't contain any hands", hole_string));
+        }
+        ranges.push(range);
     }
-    all_hole_cards
+    Ok((hole_strings, ranges))
 }
 
 const DEFAULT_NUM_SIMS: i32 = 10 * 1000;
-fn get_num_sims(matches: &Matches) -> i32 {
+fn get_num_sims(matches: &Matches) -> Result<i32, String> {
     get_numeric_arg(matches, NUM_SIMS_ARG, DEFAULT_NUM_SIMS)
 }
 
-fn get_num_threads(matches: &Matches) -> i32 {
+fn get_num_threads(matches: &Matches) -> Result<i32, String> {
     get_numeric_arg(matches, NUM_THREADS_ARG, num_cpus::get() as i32)
 }
 
-fn get_numeric_arg(matches: &Matches, arg: &str, default: i32) -> i32 {
-    if !matches.opt_present(arg) {
-        return default;
-    }
-    let num_str = matches.opt_str(arg).unwrap();
-    let num_maybe: Result<i32, _> = FromStr::from_str(&num_str);
-    match num_maybe {
-        Ok(num) => num,
-        Err(_) => {
-            println!("Could not parse {} arg as a number: {}; ignoring it.", arg, num_str);
-            default
-        }
+fn get_num_random_opponents(matches: &Matches) -> Result<usize, String> {
+    let num_opponents = get_numeric_arg(matches, RANDOM_OPPONENTS_ARG, 0)?;
+    if num_opponents < 0 {
+        return Err(format!("Can't have {} random opponents", num_opponents));
     }
+    Ok(num_opponents as usize)
 }
 
-fn parse_cards_string(cards_string: &str) -> Vec<Card> {
-    let chars: Vec<char> = cards_string.chars().collect();
-    assert!(chars.len() % 2 == 0, "Odd numbers of characters, cannot be cards: {}", cards_string);
-
-    let num_cards = chars.len() / 2;
-    let mut cards = Vec::with_capacity(num_cards);
-    for card_index in 0..num_cards {
-        let rank_index = card_index * 2;
-        let suit_index = rank_index + 1;
-        let rank_char = chars[rank_index];
-        let suit_char = chars[suit_index];
-        let rank = parse_rank(rank_char).expect(
-                &format!("Couldn't parse {} (position {} in {}) as a rank",
-                rank_char, rank_index, cards_string));
-        let suit = parse_suit(suit_char).expect(
-                &format!("Couldn't parse {} (position {} in {}) as a suit",
-                suit_char, suit_index, cards_string));
-        cards.push(card(rank, suit));
+fn get_game(matches: &Matches) -> Result<Game, String> {
+    let game_str = match matches.opt_str(GAME_ARG) {
+        Some(game_str) => game_str,
+        None => return Ok(Game::Holdem),
+    };
+    match game_str.as_ref() {
+        "holdem" => Ok(Game::Holdem),
+        "omaha" => Ok(Game::Omaha),
+        "omaha5" => Ok(Game::FiveCardOmaha),
+        "omaha6" => Ok(Game::SixCardOmaha),
+        "stud" => Ok(Game::SevenCardStud),
+        "draw" => Ok(Game::FiveCardDraw),
+        _ => Err(format!("Unknown game: {}", game_str)),
     }
-    cards
 }
 
-fn parse_rank(rank_char: char) -> Option<Rank> {
-    let rank = match rank_char {
-        'A' | 'a' => Ace,
-        'K' | 'k' => King,
-        'Q' | 'q' => Queen,
-        'J' | 'j' => Jack,
-        'T' | 't' => Ten,
-        '9' => Nine,
-        '8' => Eight,
-        '7' => Seven,
-        '6' => Six,
-        '5' => Five,
-        '4' => Four,
-        '3' => Three,
-        '2' => Two,
-        _ => return None
-    };
-    Some(rank)
+fn get_discards(matches: &Matches) -> Result<Vec<Discard>, String> {
+    matches.opt_strs(DISCARD_ARG).iter().map(|discard_string| {
+        match discard_string.as_ref() {
+            "auto" => Ok(Discard::Auto),
+            "none" => Ok(Discard::StandPat),
+            _ => parse_cards_string(discard_string).map(Discard::Cards),
+        }
+    }).collect()
 }
 
-fn parse_suit(suit_char: char) -> Option<Suit> {
-    let suit = match suit_char {
-        'S' | 's' => Spades,
-        'H' | 'h' => Hearts,
-        'C' | 'c' => Clubs,
-        'D' | 'd' => Diamonds,
-        _ => return None
+fn get_format(matches: &Matches) -> Result<Format, String> {
+    let format_str = match matches.opt_str(FORMAT_ARG) {
+        Some(format_str) => format_str,
+        None => return Ok(Format::Text),
     };
-    Some(suit)
+    match format_str.as_ref() {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        "csv" => Ok(Format::Csv),
+        _ => Err(format!("Unknown format: {}", format_str)),
+    }
 }
 
-fn insert_outcome(outcomes: &mut HashMap<Vec<i32>, HandStats>, winners: &Vec<i32>, hand: &Hand) {
-    // Set up default stats if there are none yet.
-    if let None = outcomes.get(winners) {
-        outcomes.insert(winners.clone(), HandStats::create());
+fn get_confidence(matches: &Matches) -> Result<f64, String> {
+    let confidence_str = match matches.opt_str(CONFIDENCE_ARG) {
+        Some(confidence_str) => confidence_str,
+        None => return Ok(DEFAULT_CONFIDENCE),
+    };
+    match confidence_str.parse::<f64>() {
+        Ok(confidence) if confidence > 0f64 && confidence < 1f64 => Ok(confidence),
+        _ => Err(format!("The confidence must be a number between 0 and 1, not {}", confidence_str)),
     }
-
-    outcomes.get_mut(winners).unwrap().add_event(hand);
 }
 
-const BOARD_SIZE: usize = 5;
-fn pick_random_board(initial_board: &[Card], all_hole_cards: &[[Card; 2]]) -> [Card; BOARD_SIZE] {
-    let mut board = [card(Ace, Spades); BOARD_SIZE]; // Dummies
-    for index in 0..initial_board.len() {
-        board[index] = initial_board[index];
+fn get_precision(matches: &Matches, confidence: f64) -> Result<Option<Precision>, String> {
+    let precision_str = match matches.opt_str(PRECISION_ARG) {
+        Some(precision_str) => precision_str,
+        None => return Ok(None),
+    };
+    match precision_str.parse::<f64>() {
+        Ok(percent) if percent > 0f64 => Ok(Some(Precision::create(percent / 100f64, confidence))),
+        _ => Err(format!("The precision must be a percentage above 0, not {}", precision_str)),
     }
+}
 
-    let mut used_indexes: Vec<u8> = Vec::with_capacity(all_hole_cards.len() + BOARD_SIZE);
-    let card_to_index = |card: &Card| (*card).into();
-    used_indexes.extend(
-        initial_board.iter().map(&card_to_index));
-    used_indexes.extend(
-        all_hole_cards.iter().
-        flat_map(|cards| cards). // Flatten all hands into one iterator
-        map(&card_to_index));
-
-    let mut board_index = initial_board.len();
-    let mut rng = rand::thread_rng();
-    while board_index < BOARD_SIZE {
-        /*
-        Generate random cards and skip them if they're used already.
-        The assumption is that few cards will be used compared to the
-        possible 52, so it should skip rarely and be efficient.
-        */
-        let card = rng.gen::<Card>();
-        let card_index = card.into();
-        if used_indexes.contains(&card_index) {
-            continue;
-        }
-        used_indexes.push(card_index);
-        board[board_index] = card;
-        board_index += 1;
+fn get_time_limit(matches: &Matches) -> Result<Option<Duration>, String> {
+    let seconds_str = match matches.opt_str(TIME_LIMIT_ARG) {
+        Some(seconds_str) => seconds_str,
+        None => return Ok(None),
+    };
+    match seconds_str.parse::<f64>() {
+        Ok(seconds) if seconds > 0f64 && seconds.is_finite() => Ok(Some(Duration::from_millis((seconds * 1000f64) as u64))),
+        _ => Err(format!("The time limit must be a number of seconds above 0, not {}", seconds_str)),
     }
-    board
 }
 
-fn get_num_sims_for_thread(total_num_sims: i32, total_num_threads: i32, thread_index: i32) -> i32 {
-    assert!(total_num_threads > thread_index);
-    let base_num_sims = total_num_sims / total_num_threads;
-    let threads_with_extra = total_num_sims % total_num_threads;
-    let this_threads_extra =
-        if thread_index < threads_with_extra {
-            1
-        } else {
-            0
-        };
-    base_num_sims + this_threads_extra
+fn get_seed(matches: &Matches) -> Result<Option<u64>, String> {
+    match matches.opt_str(SEED_ARG) {
+        Some(seed_str) => seed_str.parse().map(Some).map_err(|_| format!("The seed must be a whole number of at least 0, not {}", seed_str)),
+        None => Ok(None),
+    }
 }
 
-struct HandStats {
-    events: [i32; NUM_HANDS], // Number of times each hand happened
+fn duration_seconds(duration: Duration) -> f64 {
+    duration.as_secs() as f64 + f64::from(duration.subsec_nanos()) / 1e9
 }
 
-impl HandStats {
-    fn create() -> HandStats {
-        HandStats{events: [0; NUM_HANDS]}
-    }
-
-    fn add_event(&mut self, hand: &Hand) {
-        let event_index: u8 = (*hand).into();
-        self.events[event_index as usize] += 1;
+fn get_rules(matches: &Matches) -> Result<Rules, String> {
+    let rules_str = match matches.opt_str(RULES_ARG) {
+        Some(rules_str) => rules_str,
+        None => return Ok(Rules::standard()),
+    };
+    match rules_str.as_ref() {
+        "standard" => Ok(Rules::standard()),
+        "shortdeck" => Ok(Rules::short_deck()),
+        _ => Err(format!("Unknown rules: {}", rules_str)),
     }
+}
 
-    fn total_events(&self) -> i32 {
-        self.events.iter().fold(0, |aggregate, event| aggregate + event)
+fn get_mode(matches: &Matches) -> Result<SimulationMode, String> {
+    let mode_str = match matches.opt_str(MODE_ARG) {
+        Some(mode_str) => mode_str,
+        None => return Ok(SimulationMode::Auto),
+    };
+    match mode_str.as_ref() {
+        "exact" => Ok(SimulationMode::Exact),
+        "random" => Ok(SimulationMode::Random),
+        _ => Err(format!("Unknown mode: {}", mode_str)),
     }
 }
 
-fn name_outcome(outcome: &Vec<i32>, all_hole_cards: &[[Card; 2]]) -> String {
-    if outcome.len() == 1 {
-        let hand_index = outcome[0];
-        return format!("Hand {} {:?} wins", outcome[0], all_hole_cards[hand_index as usize]);
-    }
-    if outcome.len() > 0 {
-        return format!("Chop between hands {}", hands_to_string(all_hole_cards, &outcome));
-    }
-    panic!("Empty outcome")
+fn get_numeric_arg(matches: &Matches, arg: &str, default: i32) -> Result<i32, String> {
+    let num_str = match matches.opt_str(arg) {
+        Some(num_str) => num_str,
+        None => return Ok(default),
+    };
+    i32::from_str(&num_str).map_err(|_| format!("Couldn't parse the -{} arg as a number: {}", arg, num_str))
 }
 
-fn hands_to_string(hands: &[[Card; 2]], indices: &[i32]) -> String {
-    let mut string = format!("{:?}", hands[indices[0] as usize]);
-    for index in 1..indices.len() {
-        string = string + &format!(", {:?}", hands[indices[index as usize] as usize]);
-    }
-    string
+fn parse_cards_string(cards_string: &str) -> Result<Vec<Card>, String> {
+    parse_cards(cards_string).map_err(|error| format!("Couldn't parse {} as cards: {}", cards_string, error))
 }
@@ -0,0 +1,347 @@
+/*
+Writing out the results of a run, as text for people or as JSON or CSV for
+other programs.
+
+The JSON is one object, and fields are only ever added to it, never renamed
+or removed, so parsers can ignore what they don't know:
+
+    {
+      "schema_version": 1,
+      "inputs": {
+        "game": "holdem",              // As passed to -g.
+        "rules": "standard",           // As passed to -r.
+        "hi_lo": false,
+        "hands": ["AsKs", "QQ+,AKs"],  // As passed to -h, then "(random)" per random opponent.
+        "board": "2c7d9h",
+        "dead_cards": "",
+        "discards": ["auto"],          // As passed to -x.
+        "mode": "auto",                // As passed to -m.
+        "num_threads": 4,
+        "confidence": 0.95,            // As passed to -c.
+        "precision": 0.001,            // As passed to -p, but as a fraction. null without it.
+        "time_limit": 2.5,             // In seconds, as passed to -s. null without it.
+        "seed": 42                     // As passed to --seed, or null.
+      },
+      "num_sims": 10000,               // Hands simulated, which can be fewer than -n when stopping early.
+      "exact": false,                  // Whether every board was dealt exactly once.
+      "players": [
+        {
+          "index": 0,
+          "hand": "AsKs",
+          "random": false,             // Whether this is a random opponent.
+          "equity": 0.4512,            // Share of the pots won, from 0 to 1.
+          "wins": 4400,                // Whole pots won. In hi-lo, scoops.
+          "ties": 224,                 // Pots partly won.
+          "highs": 4624,               // Pots where some of the high half was won.
+          "lows": 0,                   // Pots where some of the low half was won.
+          "standard_error": 0.0049,    // Of the equity. 0 when exact.
+          "confidence_interval": [0.4416, 0.4608]
+        }
+      ],
+      "field": null,                   // Like a player, but for all the random opponents together.
+      "outcomes": [                    // The high half's winners in hi-lo. Most common first.
+        {
+          "winners": [0],              // Player indices. More than one means a chop.
+          "count": 4400,
+          "hands": {"high_card": 0, "pair": 2000, "two_pair": 0, "trips": 0, "straight": 0,
+                    "flush": 0, "full_house": 0, "quads": 0, "straight_flush": 0},
+          "frequency": 0.44,           // count out of num_sims, with its error like equity's.
+          "standard_error": 0.005,
+          "confidence_interval": [0.4303, 0.4497]
+        }
+      ],
+      "low_outcomes": []               // Only in hi-lo, like outcomes. No winners means nobody had a low.
+    }
+
+The CSV has a header row and then a row per player, plus a row for the
+field of random opponents if there are any, with the index "field":
+
+    index,hand,random,equity,wins,ties,highs,lows,num_sims,standard_error,ci_low,ci_high
+
+The confidence intervals use the normal approximation; see estimate.rs in
+the library.
+*/
+
+use std::fmt::Write;
+
+use cards::Card;
+use poker_hands::{Hand, Rules, NUM_HANDS};
+use duration_seconds;
+use poker_odds::{Discard, Estimate, EquityCalculator, Game, PlayerEquity, SimulationMode, SimulationResult};
+
+pub const SCHEMA_VERSION: i32 = 1;
+
+// The keys for each type of hand in the JSON, indexed like Hand's Into<u8>.
+const HAND_KEYS: [&str; NUM_HANDS] = ["high_card", "pair", "two_pair", "trips", "straight", "flush", "full_house", "quads", "straight_flush"];
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+// Prints the results the way people like to read them, with a margin of error for the given confidence.
+pub fn print_text(result: &SimulationResult, hand_names: &[String], confidence: f64) {
+    if !result.exact {
+        println!("Margins are for {}% confidence", confidence * 100f64);
+    }
+    for (player, equity) in result.player_equities().iter().enumerate() {
+        println!("Hand {} {}: {}", player, hand_names[player], describe_equity(result, equity, confidence));
+    }
+    if result.num_random_opponents > 0 {
+        println!("The field of {} random hands: {}", result.num_random_opponents, describe_equity(result, &result.field_equity(), confidence));
+    }
+
+    // In hi-lo, the outcomes are for the high half.
+    let high_prefix = if result.hi_lo { "High: " } else { "" };
+    for (outcome, stats) in result.sorted_outcomes() {
+        let total_events = stats.total_events();
+        let outcome_percent = result.percent_of_sims(total_events as f64);
+        let outcome_name = name_outcome(outcome, hand_names);
+        println!("{}{} ({} times, {}%{})", high_prefix, outcome_name, total_events, outcome_percent,
+                 describe_margin(&result.frequency_estimate(total_events), confidence));
+        let sorted_hand_indices = sort_descending(
+            (0..NUM_HANDS).map(|index| (index, stats.events[index])).collect());
+        for hand_index in sorted_hand_indices {
+            let hand_events = stats.events[hand_index];
+            if hand_events == 0 {
+                continue;
+            }
+            let hand_percent = (hand_events as f64 / total_events as f64) * 100f64;
+            println!("\t{}: {} times, {}%", Hand::name_hand_index(hand_index), hand_events, hand_percent);
+        }
+    }
+    for (low_outcome, low_events) in result.sorted_low_outcomes() {
+        let outcome_name = if low_outcome.is_empty() { "Nobody has a low".to_string() } else { name_outcome(&low_outcome, hand_names) };
+        println!("Low: {} ({} times, {}%{})", outcome_name, low_events, result.percent_of_sims(low_events as f64),
+                 describe_margin(&result.frequency_estimate(low_events), confidence));
+    }
+}
+
+fn describe_equity(result: &SimulationResult, equity: &PlayerEquity, confidence: f64) -> String {
+    let margin = describe_margin(&result.equity_estimate(equity), confidence);
+    if result.hi_lo {
+        format!("{}%{} equity (scoops {}%, high {}%, low {}%)",
+                result.percent_of_sims(equity.pots_won), margin,
+                result.percent_of_sims(equity.wins as f64),
+                result.percent_of_sims(equity.highs as f64),
+                result.percent_of_sims(equity.lows as f64))
+    } else {
+        format!("{}%{} equity (wins {}%, ties {}%)",
+                result.percent_of_sims(equity.pots_won), margin,
+                result.percent_of_sims(equity.wins as f64),
+                result.percent_of_sims(equity.ties as f64))
+    }
+}
+
+// Exact results have no margin, so it's left out.
+fn describe_margin(estimate: &Estimate, confidence: f64) -> String {
+    if estimate.standard_error == 0f64 {
+        String::new()
+    } else {
+        format!(" ± {:.2}%", estimate.margin(confidence) * 100f64)
+    }
+}
+
+fn sort_descending<T: Clone>(mut items: Vec<(T, i32)>) -> Vec<T> {
+    // Switch the order to get greatest-first.
+    items.sort_by_key(|&(_, count)| -count);
+    items.iter().map(|(item, _)| item.clone()).collect()
+}
+
+fn name_outcome(outcome: &[i32], hand_names: &[String]) -> String {
+    if outcome.len() == 1 {
+        let hand_index = outcome[0];
+        return format!("Hand {} {} wins", outcome[0], hand_names[hand_index as usize]);
+    }
+    if !outcome.is_empty() {
+        return format!("Chop between hands {}", hands_to_string(hand_names, outcome));
+    }
+    panic!("Empty outcome")
+}
+
+fn hands_to_string(hand_names: &[String], indices: &[i32]) -> String {
+    let names: Vec<&str> = indices.iter().map(|&index| hand_names[index as usize].as_ref()).collect();
+    names.join(", ")
+}
+
+pub fn cards_to_string(cards: &[Card]) -> String {
+    cards.iter().map(|card| card.to_string()).collect()
+}
+
+// The results, along with what was asked for, as JSON in the schema above.
+pub fn to_json(calculator: &EquityCalculator, result: &SimulationResult, hand_names: &[String], confidence: f64) -> String {
+    let mut json = String::new();
+    json.push('{');
+    write!(json, "\"schema_version\":{},", SCHEMA_VERSION).unwrap();
+
+    let quoted_names: Vec<String> = hand_names.iter().map(|name| json_string(name)).collect();
+    let quoted_discards: Vec<String> = calculator.discards.iter().map(|discard| json_string(&discard_name(discard))).collect();
+    write!(json, "\"inputs\":{{\"game\":{},\"rules\":{},\"hi_lo\":{},\"hands\":[{}],\"board\":{},\"dead_cards\":{},\"discards\":[{}],\"mode\":{},\"num_threads\":{},\"confidence\":{},\"precision\":{},\"time_limit\":{},\"seed\":{}}},",
+           json_string(game_name(calculator.game)),
+           json_string(rules_name(&calculator.rules)),
+           calculator.hi_lo,
+           quoted_names.join(","),
+           json_string(&cards_to_string(&calculator.board)),
+           json_string(&cards_to_string(&calculator.dead_cards)),
+           quoted_discards.join(","),
+           json_string(mode_name(calculator.mode)),
+           calculator.num_threads,
+           confidence,
+           calculator.precision.map_or("null".to_string(), |precision| json_number(precision.margin)),
+           calculator.time_limit.map_or("null".to_string(), |time_limit| json_number(duration_seconds(time_limit))),
+           calculator.seed.map_or("null".to_string(), |seed| seed.to_string())).unwrap();
+
+    write!(json, "\"num_sims\":{},\"exact\":{},", result.num_sims, result.exact).unwrap();
+
+    let num_known_players = result.num_players - result.num_random_opponents;
+    let players: Vec<String> = result.player_equities().iter().enumerate().map(|(player, equity)| {
+        format!("{{\"index\":{},\"hand\":{},\"random\":{},{}}}",
+                player, json_string(&hand_names[player]), player >= num_known_players, equity_json_fields(result, equity, confidence))
+    }).collect();
+    write!(json, "\"players\":[{}],", players.join(",")).unwrap();
+
+    if result.num_random_opponents > 0 {
+        write!(json, "\"field\":{{{}}},", equity_json_fields(result, &result.field_equity(), confidence)).unwrap();
+    } else {
+        json.push_str("\"field\":null,");
+    }
+
+    let outcomes: Vec<String> = result.sorted_outcomes().iter().map(|&(winners, stats)| {
+        let hands: Vec<String> = HAND_KEYS.iter().zip(stats.events.iter()).
+            map(|(key, events)| format!("\"{}\":{}", key, events)).collect();
+        let total_events = stats.total_events();
+        format!("{{\"winners\":{},\"count\":{},\"hands\":{{{}}},{}}}", json_indices(winners), total_events, hands.join(","),
+                frequency_json_fields(&result.frequency_estimate(total_events), confidence))
+    }).collect();
+    write!(json, "\"outcomes\":[{}],", outcomes.join(",")).unwrap();
+
+    let low_outcomes: Vec<String> = result.sorted_low_outcomes().iter().map(|&(ref winners, count)| {
+        format!("{{\"winners\":{},\"count\":{},{}}}", json_indices(winners), count,
+                frequency_json_fields(&result.frequency_estimate(count), confidence))
+    }).collect();
+    write!(json, "\"low_outcomes\":[{}]", low_outcomes.join(",")).unwrap();
+
+    json.push('}');
+    json
+}
+
+fn equity_json_fields(result: &SimulationResult, equity: &PlayerEquity, confidence: f64) -> String {
+    let estimate = result.equity_estimate(equity);
+    format!("\"equity\":{},\"wins\":{},\"ties\":{},\"highs\":{},\"lows\":{},{}",
+            json_number(estimate.value), equity.wins, equity.ties, equity.highs, equity.lows,
+            error_json_fields(&estimate, confidence))
+}
+
+fn frequency_json_fields(estimate: &Estimate, confidence: f64) -> String {
+    format!("\"frequency\":{},{}", json_number(estimate.value), error_json_fields(estimate, confidence))
+}
+
+fn error_json_fields(estimate: &Estimate, confidence: f64) -> String {
+    let (low, high) = estimate.confidence_interval(confidence);
+    format!("\"standard_error\":{},\"confidence_interval\":[{},{}]",
+            json_number(estimate.standard_error), json_number(low), json_number(high))
+}
+
+fn json_indices(indices: &[i32]) -> String {
+    let strings: Vec<String> = indices.iter().map(|index| index.to_string()).collect();
+    format!("[{}]", strings.join(","))
+}
+
+// JSON has no NaN or infinity, like when there were no sims, so those become null.
+fn json_number(number: f64) -> String {
+    if number.is_finite() {
+        number.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+pub fn json_string(string: &str) -> String {
+    let mut quoted = String::with_capacity(string.len() + 2);
+    quoted.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(quoted, "\\u{:04x}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// Each player's equity as CSV, in the columns described above.
+pub fn to_csv(result: &SimulationResult, hand_names: &[String], confidence: f64) -> String {
+    let mut csv = String::from("index,hand,random,equity,wins,ties,highs,lows,num_sims,standard_error,ci_low,ci_high\n");
+    let num_known_players = result.num_players - result.num_random_opponents;
+    for (player, equity) in result.player_equities().iter().enumerate() {
+        csv_row(&mut csv, &player.to_string(), &hand_names[player], player >= num_known_players, result, equity, confidence);
+    }
+    if result.num_random_opponents > 0 {
+        let field_name = format!("{} random hands", result.num_random_opponents);
+        csv_row(&mut csv, "field", &field_name, true, result, &result.field_equity(), confidence);
+    }
+    csv
+}
+
+fn csv_row(csv: &mut String, index: &str, hand: &str, random: bool, result: &SimulationResult, equity: &PlayerEquity, confidence: f64) {
+    let estimate = result.equity_estimate(equity);
+    let (ci_low, ci_high) = estimate.confidence_interval(confidence);
+    writeln!(csv, "{},{},{},{},{},{},{},{},{},{},{},{}",
+             index, csv_field(hand), random, estimate.value,
+             equity.wins, equity.ties, equity.highs, equity.lows, result.num_sims,
+             estimate.standard_error, ci_low, ci_high).unwrap();
+}
+
+// Quotes a field if it has a comma or quote in it, like ranges often do.
+pub fn csv_field(field: &str) -> String {
+    if field.contains(',') || field.contains('"') || field.contains('\n') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// The names here match the command line's.
+fn game_name(game: Game) -> &'static str {
+    match game {
+        Game::Holdem => "holdem",
+        Game::Omaha => "omaha",
+        Game::FiveCardOmaha => "omaha5",
+        Game::SixCardOmaha => "omaha6",
+        Game::SevenCardStud => "stud",
+        Game::FiveCardDraw => "draw",
+    }
+}
+
+fn rules_name(rules: &Rules) -> &'static str {
+    if *rules == Rules::short_deck() {
+        "shortdeck"
+    } else if *rules == Rules::standard() {
+        "standard"
+    } else {
+        "custom"
+    }
+}
+
+fn mode_name(mode: SimulationMode) -> &'static str {
+    match mode {
+        SimulationMode::Auto => "auto",
+        SimulationMode::Random => "random",
+        SimulationMode::Exact => "exact",
+    }
+}
+
+fn discard_name(discard: &Discard) -> String {
+    match *discard {
+        Discard::Auto => "auto".to_string(),
+        Discard::StandPat => "none".to_string(),
+        Discard::Cards(ref cards) => cards_to_string(cards),
+    }
+}
@@ -0,0 +1,47 @@
+#![cfg(test)]
+
+use output::*;
+
+use cards::parse_cards;
+use poker_odds::EquityCalculator;
+
+fn river_calculator() -> EquityCalculator {
+    let hole_cards = vec![parse_cards("AsAh").unwrap(), parse_cards("KsKh").unwrap()];
+    EquityCalculator::create(hole_cards, parse_cards("2c7d9hJc4s").unwrap(), 100)
+}
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn json_has_inputs_and_results() {
+    let calculator = river_calculator();
+    let result = calculator.run().unwrap();
+    let json = to_json(&calculator, &result, &names(&["AsAh", "KsKh"]), 0.95);
+
+    assert!(json.starts_with("{\"schema_version\":1,\"inputs\":{\"game\":\"holdem\",\"rules\":\"standard\",\"hi_lo\":false,\"hands\":[\"AsAh\",\"KsKh\"],\"board\":\"2c7d9hJc4s\","), "{}", json);
+    assert!(json.contains("\"num_sims\":1,\"exact\":true,"), "{}", json);
+    assert!(json.contains("{\"index\":0,\"hand\":\"AsAh\",\"random\":false,\"equity\":1,\"wins\":1,\"ties\":0,\"highs\":1,\"lows\":0,\"standard_error\":0,\"confidence_interval\":[1,1]}"), "{}", json);
+    assert!(json.contains("\"num_threads\":1,\"confidence\":0.95,\"precision\":null,\"time_limit\":null,\"seed\":null}"), "{}", json);
+    assert!(json.contains("\"field\":null,"), "{}", json);
+    assert!(json.contains("\"outcomes\":[{\"winners\":[0],\"count\":1,\"hands\":{\"high_card\":0,\"pair\":1,\"two_pair\":0,"), "{}", json);
+    assert!(json.ends_with("\"low_outcomes\":[]}"), "{}", json);
+}
+
+#[test]
+fn csv_has_a_row_per_player() {
+    let calculator = river_calculator();
+    let result = calculator.run().unwrap();
+    let csv = to_csv(&result, &names(&["AsAh", "KK,QQ"]), 0.95);
+    assert_eq!("index,hand,random,equity,wins,ties,highs,lows,num_sims,standard_error,ci_low,ci_high\n\
+                0,AsAh,false,1,1,0,1,0,1,0,1,1\n\
+                1,\"KK,QQ\",false,0,0,0,0,0,1,0,0,0\n", csv);
+}
+
+#[test]
+fn strings_are_escaped() {
+    assert_eq!("\"a\\\"b\\\\c\\n\\u0001\"", json_string("a\"b\\c\n\u{1}"));
+    assert_eq!("\"say \"\"AA\"\"\"", csv_field("say \"AA\""));
+    assert_eq!("QQ+", csv_field("QQ+"));
+}
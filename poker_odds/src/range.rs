@@ -0,0 +1,305 @@
+/*
+Hand ranges in the usual notation: a comma-separated list of entries like
+
+    QQ+, AKs, A2s-A5s, T9o, KJs+, AsKd, AA:0.5
+
+A pair like "QQ" means all six combos of queens, "AKs" the four suited
+ace-kings, "AKo" the twelve offsuit ones and "AK" all sixteen. A "+" raises
+the second rank up to just below the first ("KJs+" is KJs and KQs), or for
+pairs, up to aces. A "-" spans two hands with the same first rank, or two
+pairs. Exact hole cards can be given with suits, including more than two
+of them for games like Omaha, where a range is usually just one exact
+hand, or fewer for the cards seen so far in stud. Any entry can have a
+weight from 0 to 1 after a colon, saying how often that hand is played;
+entries without one get a weight of 1.
+*/
+
+use std::cmp::Reverse;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use rand::Rng;
+
+use cards::{Card, CardSet, ParseError, Rank, Suit, card, parse_cards};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeightedCombo {
+    // Highest card first.
+    pub cards: Vec<Card>,
+    pub weight: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HandRange {
+    combos: Vec<WeightedCombo>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RangeError {
+    // The entry isn't any kind of hand this notation knows about.
+    InvalidEntry(String),
+    // The entry is too long for anything but exact cards, and these are what's wrong with them.
+    InvalidCards(String, ParseError),
+    // The weight after the colon isn't a number from 0 to 1.
+    InvalidWeight(String),
+}
+
+impl Display for RangeError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match *self {
+            RangeError::InvalidEntry(ref entry) => write!(f, "can't understand range entry \"{}\"", entry),
+            RangeError::InvalidCards(ref entry, error) => write!(f, "bad cards in range entry \"{}\": {}", entry, error),
+            RangeError::InvalidWeight(ref entry) => write!(f, "bad weight in range entry \"{}\"", entry),
+        }
+    }
+}
+
+impl Error for RangeError {}
+
+impl HandRange {
+    // A range of exactly one combo.
+    pub fn from_cards<C: AsRef<[Card]>>(cards: C) -> HandRange {
+        HandRange{combos: vec![WeightedCombo{cards: sorted_combo(cards.as_ref()), weight: 1f64}]}
+    }
+
+    pub fn combos(&self) -> &[WeightedCombo] {
+        &self.combos
+    }
+
+    pub fn num_combos(&self) -> usize {
+        self.combos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    // The number of combos, counting each according to its weight.
+    pub fn total_weight(&self) -> f64 {
+        self.combos.iter().map(|combo| combo.weight).sum()
+    }
+
+    // The same range, minus any combos using cards that are known to be elsewhere.
+    pub fn without(&self, dead_cards: &CardSet) -> HandRange {
+        let combos = self.combos.iter().
+            filter(|combo| combo.cards.iter().all(|&card| !dead_cards.contains(card))).
+            cloned().
+            collect();
+        HandRange{combos}
+    }
+
+    // Adds a combo, replacing its weight if it's already there.
+    fn add(&mut self, cards: &[Card], weight: f64) {
+        let cards = sorted_combo(cards);
+        match self.combos.iter_mut().find(|combo| combo.cards == cards) {
+            Some(combo) => combo.weight = weight,
+            None => self.combos.push(WeightedCombo{cards, weight}),
+        }
+    }
+}
+
+impl FromStr for HandRange {
+    type Err = RangeError;
+
+    fn from_str(range_string: &str) -> Result<HandRange, RangeError> {
+        let mut range = HandRange{combos: Vec::new()};
+        for entry in range_string.split(',') {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                continue;
+            }
+            let (hands, weight) = parse_entry(entry)?;
+            // Weightless combos never get dealt, so don't bother keeping them.
+            if weight == 0f64 {
+                continue;
+            }
+            for cards in hands {
+                range.add(&cards, weight);
+            }
+        }
+        Ok(range)
+    }
+}
+
+fn sorted_combo(cards: &[Card]) -> Vec<Card> {
+    let mut sorted = cards.to_vec();
+    sorted.sort_by_key(|&card| Reverse(card));
+    sorted
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Suitedness {
+    Suited,
+    Offsuit,
+    Either,
+}
+
+// Two ranks, without suits: "AKs", "T9", "QQ" and so on. The first rank is the higher one.
+#[derive(Copy, Clone)]
+struct HandClass {
+    hi_rank: Rank,
+    lo_rank: Rank,
+    suitedness: Suitedness,
+}
+
+impl HandClass {
+    fn is_pair(&self) -> bool {
+        self.hi_rank == self.lo_rank
+    }
+
+    fn combos(&self) -> Vec<Vec<Card>> {
+        let suits = [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds];
+        let mut combos = Vec::with_capacity(16);
+        for (hi_index, &hi_suit) in suits.iter().enumerate() {
+            for (lo_index, &lo_suit) in suits.iter().enumerate() {
+                let wanted = if self.is_pair() {
+                    // Count each pair of suits once.
+                    hi_index < lo_index
+                } else {
+                    match self.suitedness {
+                        Suitedness::Suited => hi_suit == lo_suit,
+                        Suitedness::Offsuit => hi_suit != lo_suit,
+                        Suitedness::Either => true,
+                    }
+                };
+                if wanted {
+                    combos.push(vec![card(self.hi_rank, hi_suit), card(self.lo_rank, lo_suit)]);
+                }
+            }
+        }
+        combos
+    }
+}
+
+fn parse_entry(entry: &str) -> Result<(Vec<Vec<Card>>, f64), RangeError> {
+    let invalid_entry = || RangeError::InvalidEntry(entry.to_string());
+
+    let (hands, weight) = match entry.find(':') {
+        Some(colon) => {
+            let weight_string = entry[(colon + 1)..].trim();
+            let weight: f64 = weight_string.parse().map_err(|_| RangeError::InvalidWeight(entry.to_string()))?;
+            if !(0f64..=1f64).contains(&weight) {
+                return Err(RangeError::InvalidWeight(entry.to_string()));
+            }
+            (entry[..colon].trim(), weight)
+        },
+        None => (entry, 1f64),
+    };
+
+    // Exact hole cards, like "AsKd".
+    let card_error = match parse_cards(hands) {
+        Ok(cards) => {
+            let all_different = cards.iter().cloned().collect::<CardSet>().len() == cards.len();
+            if !cards.is_empty() && all_different {
+                return Ok((vec![cards], weight));
+            }
+            return Err(invalid_entry());
+        },
+        Err(error) => error,
+    };
+
+    let classes = if let Some(dash) = hands.find('-') {
+        let first = parse_class(&hands[..dash]).ok_or_else(invalid_entry)?;
+        let last = parse_class(&hands[(dash + 1)..]).ok_or_else(invalid_entry)?;
+        span_classes(first, last).ok_or_else(invalid_entry)?
+    } else if let Some(stripped) = hands.strip_suffix('+') {
+        let first = parse_class(stripped).ok_or_else(invalid_entry)?;
+        let last = if first.is_pair() {
+            HandClass{hi_rank: Rank::Ace, lo_rank: Rank::Ace, suitedness: first.suitedness}
+        } else {
+            HandClass{lo_rank: Rank::from(first.hi_rank as u8 - 1), ..first}
+        };
+        span_classes(first, last).ok_or_else(invalid_entry)?
+    } else if hands.chars().count() > MAX_CLASS_LENGTH {
+        return Err(RangeError::InvalidCards(entry.to_string(), card_error));
+    } else {
+        vec![parse_class(hands).ok_or_else(invalid_entry)?]
+    };
+
+    let hands = classes.iter().flat_map(|class| class.combos()).collect();
+    Ok((hands, weight))
+}
+
+// Like "AKs".
+const MAX_CLASS_LENGTH: usize = 3;
+
+fn parse_class(class_string: &str) -> Option<HandClass> {
+    let chars: Vec<char> = class_string.trim().chars().collect();
+    if chars.len() < 2 || chars.len() > MAX_CLASS_LENGTH {
+        return None;
+    }
+    let first: Rank = chars[0].to_string().parse().ok()?;
+    let second: Rank = chars[1].to_string().parse().ok()?;
+    let suitedness = match chars.get(2) {
+        None => Suitedness::Either,
+        Some(&'s') | Some(&'S') => Suitedness::Suited,
+        Some(&'o') | Some(&'O') => Suitedness::Offsuit,
+        Some(_) => return None,
+    };
+    if first == second && suitedness != Suitedness::Either {
+        // There's no such thing as suited or offsuit pairs.
+        return None;
+    }
+    let (hi_rank, lo_rank) = if first >= second { (first, second) } else { (second, first) };
+    Some(HandClass{hi_rank, lo_rank, suitedness})
+}
+
+// All the classes from one to the other, which have to be the same kind of hand.
+fn span_classes(first: HandClass, last: HandClass) -> Option<Vec<HandClass>> {
+    if first.is_pair() != last.is_pair() || first.suitedness != last.suitedness {
+        return None;
+    }
+    let (start, end) = if first.is_pair() {
+        (first.hi_rank as u8, last.hi_rank as u8)
+    } else {
+        if first.hi_rank != last.hi_rank {
+            return None;
+        }
+        (first.lo_rank as u8, last.lo_rank as u8)
+    };
+    let (start, end) = if start <= end { (start, end) } else { (end, start) };
+
+    let classes = (start..(end + 1)).map(|rank_index| {
+        let rank = Rank::from(rank_index);
+        if first.is_pair() {
+            HandClass{hi_rank: rank, lo_rank: rank, suitedness: first.suitedness}
+        } else {
+            HandClass{lo_rank: rank, ..first}
+        }
+    }).collect();
+    Some(classes)
+}
+
+// Picks combos from a range, more or less often according to their weights.
+pub struct ComboSampler {
+    combos: Vec<Vec<Card>>,
+    // The total weight of each combo and all the ones before it.
+    cumulative_weights: Vec<f64>,
+}
+
+impl ComboSampler {
+    pub fn create(range: &HandRange) -> ComboSampler {
+        assert!(!range.is_empty(), "Can't pick combos from an empty range");
+        let combos = range.combos.iter().map(|combo| combo.cards.clone()).collect();
+        let mut total_weight = 0f64;
+        let cumulative_weights = range.combos.iter().map(|combo| {
+            total_weight += combo.weight;
+            total_weight
+        }).collect();
+        ComboSampler{combos, cumulative_weights}
+    }
+
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> &[Card] {
+        if self.combos.len() == 1 {
+            return &self.combos[0];
+        }
+        let total_weight = self.cumulative_weights[self.cumulative_weights.len() - 1];
+        let target = rng.gen::<f64>() * total_weight;
+        // The first combo whose cumulative weight goes past the target.
+        let index = match self.cumulative_weights.binary_search_by(|weight| weight.partial_cmp(&target).unwrap()) {
+            Ok(index) => index + 1,
+            Err(index) => index,
+        };
+        &self.combos[index.min(self.combos.len() - 1)]
+    }
+}
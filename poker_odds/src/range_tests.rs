@@ -0,0 +1,101 @@
+#![cfg(test)]
+
+use super::*;
+use range::ComboSampler;
+
+use cards::{CardSet, ParseError, card};
+use cards::Rank::*;
+use cards::Suit::*;
+
+fn num_combos(range_string: &str) -> usize {
+    let range: HandRange = range_string.parse().unwrap();
+    range.num_combos()
+}
+
+#[test]
+fn single_hands() {
+    assert_eq!(6, num_combos("QQ"));
+    assert_eq!(4, num_combos("AKs"));
+    assert_eq!(12, num_combos("T9o"));
+    assert_eq!(16, num_combos("KA"));
+    assert_eq!(1, num_combos("AsKd"));
+}
+
+#[test]
+fn spans_of_hands() {
+    assert_eq!(18, num_combos("QQ+"));
+    assert_eq!(24, num_combos("22-55"));
+    assert_eq!(16, num_combos("A2s-A5s"));
+    assert_eq!(16, num_combos("A5s-A2s"));
+    assert_eq!(8, num_combos("KJs+"));
+    assert_eq!(4, num_combos("T9s+"));
+    assert_eq!(12 * 12, num_combos("A2o+"));
+}
+
+#[test]
+fn lists_of_hands() {
+    assert_eq!(6 * 13 + 4 + 12, num_combos("22+, AKs, AQo"));
+    // Overlapping entries don't count twice.
+    assert_eq!(18, num_combos("QQ+,KK"));
+    assert_eq!(0, num_combos(""));
+}
+
+#[test]
+fn weights() {
+    let range: HandRange = "AA:0.5, KK".parse().unwrap();
+    assert_eq!(12, range.num_combos());
+    assert_eq!(9f64, range.total_weight());
+
+    // Later entries override earlier weights.
+    let range: HandRange = "QQ+:0.5, AA".parse().unwrap();
+    assert_eq!(18, range.num_combos());
+    assert_eq!(12f64, range.total_weight());
+
+    let range: HandRange = "AA:0, KK".parse().unwrap();
+    assert_eq!(6, range.num_combos());
+}
+
+#[test]
+fn card_removal() {
+    let range: HandRange = "AA, AKs".parse().unwrap();
+    let dead: CardSet = vec![card(Ace, Spades), card(King, Hearts)].into_iter().collect();
+    let live_range = range.without(&dead);
+    // Three aces make three pairs, and only clubs and diamonds are left for the suited hands.
+    assert_eq!(3 + 2, live_range.num_combos());
+    for combo in live_range.combos() {
+        assert!(!dead.contains(combo.cards[0]) && !dead.contains(combo.cards[1]));
+        assert!(combo.cards[0] > combo.cards[1]);
+    }
+}
+
+#[test]
+fn bad_ranges() {
+    assert_eq!(Err(RangeError::InvalidEntry("QQs".to_string())), "AA, QQs".parse::<HandRange>());
+    assert_eq!(Err(RangeError::InvalidEntry("AK-QJ".to_string())), "AK-QJ".parse::<HandRange>());
+    assert_eq!(Err(RangeError::InvalidEntry("22-AKs".to_string())), "22-AKs".parse::<HandRange>());
+    assert_eq!(Err(RangeError::InvalidEntry("AsAs".to_string())), "AsAs".parse::<HandRange>());
+    assert_eq!(Err(RangeError::InvalidEntry("AXs".to_string())), "AXs".parse::<HandRange>());
+    // Too long to be anything but exact cards, so the card error is passed on.
+    assert_eq!(Err(RangeError::InvalidCards("AsXh".to_string(), ParseError::InvalidRank{position: 2, found: 'X'})), "AsXh".parse::<HandRange>());
+    assert_eq!(Err(RangeError::InvalidCards("2c7x:0.5".to_string(), ParseError::InvalidSuit{position: 3, found: 'x'})), "AA, 2c7x:0.5".parse::<HandRange>());
+    assert_eq!(Err(RangeError::InvalidWeight("AA:1.5".to_string())), "AA:1.5".parse::<HandRange>());
+    assert_eq!(Err(RangeError::InvalidWeight("AA:x".to_string())), "AA:x".parse::<HandRange>());
+}
+
+#[test]
+fn sampling_follows_weights() {
+    let range: HandRange = "AsAh:0.25, KsKh".parse().unwrap();
+    let sampler = ComboSampler::create(&range);
+    let mut rng = rand::thread_rng();
+    let num_aces = (0..10000).filter(|_| sampler.sample(&mut rng)[0].rank == Ace).count();
+    // Should be about a fifth.
+    assert!(num_aces > 1700 && num_aces < 2300, "Picked aces {} times", num_aces);
+}
+
+#[test]
+fn bigger_hands() {
+    let range: HandRange = "AsKd9h8h".parse().unwrap();
+    assert_eq!(1, range.num_combos());
+    assert_eq!(vec![card(Ace, Spades), card(King, Diamonds), card(Nine, Hearts), card(Eight, Hearts)], range.combos()[0].cards);
+    assert_eq!(Err(RangeError::InvalidEntry("AsKdAs8h".to_string())), "AsKdAs8h".parse::<HandRange>());
+}
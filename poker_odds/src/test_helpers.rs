@@ -0,0 +1,9 @@
+#![cfg(test)]
+
+// Helpers shared by the tests in this crate.
+
+use cards::{Card, parse_cards};
+
+pub fn cards(cards_string: &str) -> Vec<Card> {
+    parse_cards(cards_string).unwrap()
+}
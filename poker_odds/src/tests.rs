@@ -0,0 +1,520 @@
+#![cfg(test)]
+
+use super::*;
+use test_helpers::cards;
+
+use cards::card;
+use cards::Rank::*;
+use cards::Suit::*;
+
+const ACES_AND_KINGS: [&str; 2] = ["AsAh", "KsKh"];
+
+// A calculator for the game, with each hand's known cards or range.
+fn calculator_for(game: Game, hands: &[&str], board: &str, num_sims: i32) -> EquityCalculator {
+    let ranges = hands.iter().map(|hand| hand.parse().unwrap()).collect();
+    let mut calculator = EquityCalculator::create_with_ranges(ranges, cards(board), num_sims);
+    calculator.game = game;
+    calculator
+}
+
+#[test]
+fn full_board_needs_one_sim() {
+    let result = calculator_for(Game::Holdem, &ACES_AND_KINGS, "2c7d9hJc4s", 1000).run().unwrap();
+
+    assert_eq!(1, result.num_sims);
+    assert_eq!(1, result.outcomes.len());
+    assert_eq!(1, result.outcomes[&vec![0]].total_events());
+}
+
+#[test]
+fn every_sim_has_an_outcome() {
+    let mut calculator = calculator_for(Game::Holdem, &ACES_AND_KINGS, "", 1001);
+    calculator.num_threads = 3;
+    let result = calculator.run().unwrap();
+
+    assert_eq!(1001, result.num_sims);
+    let total: i32 = result.outcomes.values().map(|stats| stats.total_events()).sum();
+    assert_eq!(1001, total);
+}
+
+#[test]
+fn sims_split_between_threads() {
+    for num_threads in 1..10 {
+        let total: i32 = (0..num_threads).map(|index| get_num_sims_for_thread(100, num_threads, index)).sum();
+        assert_eq!(100, total);
+    }
+}
+
+#[test]
+fn exact_river_counts() {
+    let mut calculator = calculator_for(Game::Holdem, &ACES_AND_KINGS, "2c7d9hJc", 10);
+    calculator.num_threads = 3;
+    assert!(calculator.is_exact());
+    let result = calculator.run().unwrap();
+
+    assert!(result.exact);
+    assert_eq!(44, result.num_sims);
+    assert_eq!(42, result.outcomes[&vec![0]].total_events());
+    // Only the two remaining kings save the underdog.
+    assert_eq!(2, result.outcomes[&vec![1]].total_events());
+}
+
+#[test]
+fn mode_can_be_forced() {
+    let mut calculator = calculator_for(Game::Holdem, &ACES_AND_KINGS, "", 10);
+    assert_eq!(1712304, calculator.num_board_completions());
+    assert!(!calculator.is_exact());
+
+    calculator.board = cards("2c7d9h");
+    assert!(calculator.is_exact());
+    calculator.mode = SimulationMode::Random;
+    assert!(!calculator.is_exact());
+    assert_eq!(10, calculator.total_num_sims());
+    calculator.mode = SimulationMode::Exact;
+    assert_eq!(990, calculator.total_num_sims());
+}
+
+#[test]
+fn combinations_are_all_visited() {
+    let cards: Vec<Card> = CardSet::full().iter().collect();
+    let mut count = 0;
+    let mut last: Vec<Card> = Vec::new();
+    for_each_combination(&cards[..10], 3, |chosen| {
+        assert!(chosen.len() == 3);
+        assert!(chosen[0] < chosen[1] && chosen[1] < chosen[2]);
+        assert!(last.as_slice() != chosen);
+        last = chosen.to_vec();
+        count += 1;
+    });
+    assert_eq!(num_combinations(10, 3), count);
+    assert_eq!(120, count);
+}
+
+#[test]
+fn chops_split_equity() {
+    // The ace makes Broadway, which only gets chopped when another ace comes.
+    let result = calculator_for(Game::Holdem, &["Ah2c", "3d2s"], "TsJdQcKh", 10).run().unwrap();
+    let equities = result.player_equities();
+
+    assert_eq!(2, equities.len());
+    assert_eq!(41, equities[0].wins);
+    assert_eq!(3, equities[0].ties);
+    assert_eq!(42.5, equities[0].pots_won);
+    assert_eq!(0, equities[1].wins);
+    assert_eq!(3, equities[1].ties);
+    assert_eq!(1.5, equities[1].pots_won);
+}
+
+#[test]
+fn chops_count_toward_the_error() {
+    let mut calculator = calculator_for(Game::Holdem, &["Ah2c", "3d2s"], "TsJdQcKh", 10);
+    let exact_result = calculator.run().unwrap();
+    let exact_equity = &exact_result.player_equities()[0];
+    assert_eq!(41.75, exact_equity.pots_won_squared);
+    assert_eq!(0f64, exact_result.equity_estimate(exact_equity).standard_error);
+
+    calculator.mode = SimulationMode::Random;
+    calculator.num_sims = 10 * 1000;
+    let result = calculator.run().unwrap();
+    let equity = &result.player_equities()[0];
+    let estimate = result.equity_estimate(equity);
+    let mean = equity.pots_won / 10000f64;
+    let mean_of_squares = equity.pots_won_squared / 10000f64;
+    assert_eq!(((mean_of_squares - mean * mean) / 10000f64).sqrt(), estimate.standard_error);
+    // Far enough out that this never fails by chance.
+    assert!((estimate.value - 42.5 / 44f64).abs() < 6f64 * estimate.standard_error, "{:?}", estimate);
+
+    let outcome_estimate = result.frequency_estimate(equity.wins);
+    let (low, high) = outcome_estimate.confidence_interval(0.95);
+    assert!(low < outcome_estimate.value && outcome_estimate.value < high);
+}
+
+#[test]
+fn ranges_against_ranges() {
+    let mut calculator = calculator_for(Game::Holdem, &["AA", "KK"], "", 20 * 1000);
+    calculator.num_threads = 2;
+    assert!(!calculator.is_exact());
+    let result = calculator.run().unwrap();
+
+    let equities = result.player_equities();
+    let aces_equity = result.percent_of_sims(equities[0].pots_won);
+    assert!(aces_equity > 79f64 && aces_equity < 85f64, "Aces had {}% equity", aces_equity);
+}
+
+#[test]
+fn known_cards_are_removed_from_ranges() {
+    // The only aces left for the range are the black ones, which always chop with the red ones.
+    let result = calculator_for(Game::Holdem, &["AhAd", "AA"], "2c7d9hJs4s", 100).run().unwrap();
+
+    assert_eq!(1, result.outcomes.len());
+    assert_eq!(100, result.outcomes[&vec![0, 1]].total_events());
+}
+
+#[test]
+fn board_can_use_up_a_range() {
+    let result = calculator_for(Game::Holdem, &["AsKs", "QdQh,QdQs"], "Qd7d9h", 100).run();
+    assert_eq!(Some(SimulationError::EmptyRange{player: 1}), result.err());
+}
+
+#[test]
+fn random_opponents() {
+    let mut calculator = calculator_for(Game::Holdem, &["AsAh"], "2c7d9hJs4s", 20 * 1000);
+    calculator.num_random_opponents = 3;
+    calculator.num_threads = 2;
+    // Even with a full board, the opponents' cards are still to come.
+    assert!(!calculator.is_exact());
+    assert_eq!(20 * 1000, calculator.total_num_sims());
+    let result = calculator.run().unwrap();
+
+    assert_eq!(4, result.num_players);
+    let equities = result.player_equities();
+    assert_eq!(4, equities.len());
+    let field = result.field_equity();
+    assert_eq!(20 * 1000, equities[0].wins + field.wins + field.ties);
+    assert!((20f64 * 1000f64 - equities[0].pots_won - field.pots_won).abs() < 1e-6);
+    let opponents_pots: f64 = equities[1..].iter().map(|equity| equity.pots_won).sum();
+    assert!((opponents_pots - field.pots_won).abs() < 1e-6);
+    let aces_equity = result.percent_of_sims(equities[0].pots_won);
+    assert!(aces_equity > 62f64 && aces_equity < 72f64, "Aces had {}% equity", aces_equity);
+}
+
+#[test]
+fn dead_cards_are_never_dealt() {
+    let mut calculator = calculator_for(Game::Holdem, &ACES_AND_KINGS, "2c7d9hJc", 10);
+    calculator.dead_cards = cards("Kd3s");
+    let result = calculator.run().unwrap();
+
+    assert!(result.exact);
+    assert_eq!(42, result.num_sims);
+    assert_eq!(41, result.outcomes[&vec![0]].total_events());
+    assert_eq!(1, result.outcomes[&vec![1]].total_events());
+
+    calculator.mode = SimulationMode::Random;
+    calculator.num_sims = 2000;
+    let result = calculator.run().unwrap();
+    assert_eq!(2000, result.num_sims);
+    assert!(result.outcomes[&vec![1]].total_events() < 2000 / 10);
+}
+
+#[test]
+fn dead_cards_cant_be_on_the_board() {
+    let mut calculator = calculator_for(Game::Holdem, &ACES_AND_KINGS, "2c7d9h", 10);
+    calculator.dead_cards = cards("9h");
+    assert_eq!(Some(SimulationError::DuplicateCard(card(Nine, Hearts))), calculator.run().err());
+}
+
+#[test]
+fn hole_cards_cant_be_shared() {
+    let result = calculator_for(Game::Holdem, &["AsAh", "KsAh"], "", 10).run();
+    assert_eq!(Some(SimulationError::DuplicateCard(card(Ace, Hearts))), result.err());
+
+    let result = calculator_for(Game::Holdem, &[], "", 10).run();
+    assert_eq!(Some(SimulationError::NoPlayers), result.err());
+}
+
+#[test]
+fn omaha_uses_two_hole_cards() {
+    // In hold'em the ace of spades would make a royal flush, but Omaha needs two spades in the hole for any flush.
+    let mut calculator = calculator_for(Game::Omaha, &["As9h8c7d", "3s4s5h6h"], "KsQsJsTs2d", 10);
+    let result = calculator.run().unwrap();
+
+    assert_eq!(1, result.num_sims);
+    assert_eq!(1, result.outcomes[&vec![1]].events[5]);
+
+    calculator.game = Game::Holdem;
+    let expected_error = SimulationError::WrongNumberOfHoleCards{player: 0, expected: 2, found: 4};
+    assert_eq!(Some(expected_error), calculator.run().err());
+}
+
+#[test]
+fn omaha_against_random_hands() {
+    let mut calculator = calculator_for(Game::Omaha, &["AsAhKsKh"], "", 5000);
+    calculator.num_random_opponents = 1;
+    let result = calculator.run().unwrap();
+
+    let equity = result.percent_of_sims(result.player_equities()[0].pots_won);
+    assert!(equity > 65f64 && equity < 80f64, "Double-suited aces and kings had {}% equity", equity);
+}
+
+#[test]
+fn hi_lo_quartering() {
+    // Trip kings take the high half, and the two 7-4-3-2-A lows split the low half.
+    let mut calculator = calculator_for(Game::Omaha, &["As4sQhQc", "Ad4dJsJc", "Kh9h9s8c"], "2h3c7dKsKd", 10);
+    calculator.hi_lo = true;
+    let result = calculator.run().unwrap();
+    assert_eq!(1, result.split_outcomes[&(vec![2], vec![0, 1])]);
+    let equities = result.player_equities();
+    assert_eq!(0.25, equities[0].pots_won);
+    assert_eq!(0.25, equities[1].pots_won);
+    assert_eq!(0.5, equities[2].pots_won);
+    assert_eq!((0, 1, 0, 1), (equities[0].wins, equities[0].ties, equities[0].highs, equities[0].lows));
+    assert_eq!((0, 1, 1, 0), (equities[2].wins, equities[2].ties, equities[2].highs, equities[2].lows));
+}
+
+#[test]
+fn hi_lo_scoops_without_a_low() {
+    let mut calculator = calculator_for(Game::Omaha, &["As4sJhJc", "Kh9h9s8c"], "2h3cQdKsKd", 10);
+    calculator.hi_lo = true;
+    let result = calculator.run().unwrap();
+    assert_eq!(vec![(Vec::new(), 1)], result.sorted_low_outcomes());
+    let equities = result.player_equities();
+    assert_eq!((1, 0, 1, 0), (equities[1].wins, equities[1].ties, equities[1].highs, equities[1].lows));
+    assert_eq!(1f64, equities[1].pots_won);
+}
+
+#[test]
+fn hi_lo_pots_add_up() {
+    let mut calculator = calculator_for(Game::Omaha, &["As2sKhQh", "7c8d9hTs"], "", 2000);
+    calculator.hi_lo = true;
+    calculator.num_random_opponents = 1;
+    // Seeded so the hands dealt are always the same.
+    calculator.seed = Some(16);
+    let result = calculator.run().unwrap();
+    let equities = result.player_equities();
+    let total_pots: f64 = equities.iter().map(|equity| equity.pots_won).sum();
+    assert!((2000f64 - total_pots).abs() < 1e-6);
+    // Every high half goes to someone, and so does every low half there is.
+    let num_low_pots: i32 = result.sorted_low_outcomes().iter().filter(|&(winners, _)| !winners.is_empty()).map(|&(_, num_events)| num_events).sum();
+    assert!(equities.iter().map(|equity| equity.highs).sum::<i32>() >= 2000);
+    assert!(equities.iter().map(|equity| equity.lows).sum::<i32>() >= num_low_pots);
+    // Ace-deuce makes most of the lows.
+    assert!(equities[0].lows > equities[1].lows + equities[2].lows);
+}
+
+#[test]
+fn short_deck_flushes_beat_full_houses() {
+    let mut calculator = calculator_for(Game::Holdem, &["KdKs", "Th7h"], "AhAsKh9h6c", 1000);
+    calculator.rules = Rules::short_deck();
+    let result = calculator.run().unwrap();
+    assert_eq!(1, result.outcomes[&vec![1]].events[5]);
+
+    calculator.rules = Rules::standard();
+    let result = calculator.run().unwrap();
+    assert_eq!(1, result.outcomes[&vec![0]].events[6]);
+}
+
+#[test]
+fn short_deck_deals_from_thirty_six_cards() {
+    let mut calculator = calculator_for(Game::Holdem, &ACES_AND_KINGS, "9c8d6h", 1000);
+    calculator.rules = Rules::short_deck();
+    assert!(calculator.is_exact());
+    // 36 cards minus the 7 known ones leaves 29, for 406 turns and rivers.
+    assert_eq!(406, calculator.num_board_completions());
+    let result = calculator.run().unwrap();
+    assert_eq!(406, result.num_sims);
+
+}
+
+#[test]
+fn short_deck_aces_make_low_straights() {
+    let mut calculator = calculator_for(Game::Holdem, &["9cQs", "KsKc"], "Ad7c8s6dKh", 1000);
+    calculator.rules = Rules::short_deck();
+    let result = calculator.run().unwrap();
+    assert_eq!(1, result.outcomes[&vec![0]].events[4]);
+
+    calculator.rules = Rules::standard();
+    let result = calculator.run().unwrap();
+    assert_eq!(1, result.outcomes[&vec![1]].events[3]);
+}
+
+#[test]
+fn short_deck_has_no_low_cards() {
+    let mut calculator = calculator_for(Game::Holdem, &["2cAh", "KsKh"], "", 10);
+    calculator.rules = Rules::short_deck();
+    assert_eq!(Err(SimulationError::NotInDeck(card(Two, Clubs))), calculator.validate());
+
+    let mut calculator = calculator_for(Game::Holdem, &["AA", "22-55"], "", 10);
+    calculator.rules = Rules::short_deck();
+    assert_eq!(Err(SimulationError::EmptyRange{player: 1}), calculator.validate());
+}
+
+#[test]
+fn stud_deals_the_rest_of_each_hand() {
+    let calculator = calculator_for(Game::SevenCardStud, &["AsAhKsKhQd2c", "QsQhQcJdJh3c4c"], "", 2000);
+    assert!(!calculator.is_exact());
+    let result = calculator.run().unwrap();
+    // Any of the last two aces or kings fills up the two pair.
+    let equity = result.player_equities()[0].pots_won / result.num_sims as f64;
+    assert!(equity > 0.05 && equity < 0.2, "{}", equity);
+
+    let result = calculator_for(Game::SevenCardStud, &["AsAhKsKhQd2c7d", "QsQhQcJdJh3c4c"], "", 2000).run().unwrap();
+    assert_eq!(1, result.num_sims);
+    assert_eq!(1, result.outcomes[&vec![1]].events[6]);
+
+    let result = calculator_for(Game::SevenCardStud, &["AsAh", "7d"], "", 2000).run().unwrap();
+    assert_eq!(2000, result.num_sims);
+}
+
+#[test]
+fn stud_exposed_cards_are_dead() {
+    let mut calculator = calculator_for(Game::SevenCardStud, &["AsAhKsKhQd2c", "QsQhQcJdJh3c4c"], "", 2000);
+    calculator.dead_cards = cards("AdAcKdKc");
+    let result = calculator.run().unwrap();
+    assert!(!result.outcomes.contains_key(&vec![0]));
+}
+
+#[test]
+fn stud_hands_have_seven_cards() {
+    let mut calculator = calculator_for(Game::SevenCardStud, &ACES_AND_KINGS, "", 2000);
+    calculator.board = cards("2c3c4c");
+    assert_eq!(Err(SimulationError::TooManyBoardCards{allowed: 0, found: 3}), calculator.validate());
+    assert_eq!(Err(SimulationError::WrongNumberOfHoleCards{player: 1, expected: 7, found: 8}),
+               calculator_for(Game::SevenCardStud, &["AsAh", "KsKhKdKc2c3c4c5c"], "", 2000).validate());
+    let mut calculator = calculator_for(Game::SevenCardStud, &ACES_AND_KINGS, "", 2000);
+    calculator.num_random_opponents = 6;
+    assert_eq!(Err(SimulationError::NotEnoughCards{num_players: 8}), calculator.validate());
+}
+
+#[test]
+fn stud_hi_lo() {
+    let mut calculator = calculator_for(Game::SevenCardStud, &["KsKhKd9c9d8s7s", "Ac2d3h4s6cJdQd"], "", 2000);
+    calculator.hi_lo = true;
+    let result = calculator.run().unwrap();
+    assert_eq!(1, result.num_sims);
+    // The full house takes the high half and the six-four low takes the other.
+    let equities = result.player_equities();
+    assert_eq!(0.5, equities[0].pots_won);
+    assert_eq!(0.5, equities[1].pots_won);
+}
+
+// A pair of sevens with three kickers against trip nines.
+const SEVENS_AND_NINES: [&str; 2] = ["7s7dAhKc2s", "9s9d9h5c3s"];
+
+#[test]
+fn drawing_three_to_a_pair() {
+    let mut calculator = calculator_for(Game::FiveCardDraw, &SEVENS_AND_NINES, "", 4000);
+    calculator.discards = vec![Discard::Cards(cards("AhKc2s")), Discard::StandPat];
+    assert!(!calculator.is_exact());
+    let result = calculator.run().unwrap();
+    // Only a full house or quads beats the trips, which comes in about 1.4% of the time.
+    let equity = result.player_equities()[0].pots_won / result.num_sims as f64;
+    assert!(equity > 0.005 && equity < 0.03, "{}", equity);
+
+    calculator.discards = vec![Discard::StandPat, Discard::StandPat];
+    let result = calculator.run().unwrap();
+    assert_eq!(1, result.num_sims);
+    assert_eq!(1, result.outcomes[&vec![1]].events[3]);
+}
+
+#[test]
+fn drawing_with_the_built_in_strategy() {
+    let mut calculator = calculator_for(Game::FiveCardDraw, &["AsKsQsJs2d"], "", 4000);
+    calculator.num_random_opponents = 1;
+    let result = calculator.run().unwrap();
+    assert_eq!(4000, result.num_sims);
+    let total: i32 = result.outcomes.values().map(|stats| stats.total_events()).sum();
+    assert_eq!(4000, total);
+    // Drawing to a royal flush made a flush or straight flush in about a fifth of the hands.
+    let flushes: i32 = result.outcomes.values().map(|stats| stats.events[5] + stats.events[8]).sum();
+    assert!(flushes > 4000 / 10, "{}", flushes);
+}
+
+#[test]
+fn discards_must_be_from_the_hand() {
+    let mut calculator = calculator_for(Game::FiveCardDraw, &SEVENS_AND_NINES, "", 10);
+    calculator.discards = vec![Discard::Cards(cards("Qh"))];
+    assert_eq!(Err(SimulationError::InvalidDiscard{player: 0}), calculator.validate());
+    calculator.discards = vec![Discard::Auto];
+    calculator.game = Game::Holdem;
+    assert_eq!(Err(SimulationError::NotADrawGame), calculator.validate());
+    let mut calculator = calculator_for(Game::FiveCardDraw, &SEVENS_AND_NINES[..1], "", 10);
+    calculator.num_random_opponents = 5;
+    // Six players might need up to nine cards each.
+    assert_eq!(Err(SimulationError::NotEnoughCards{num_players: 6}), calculator.validate());
+}
+
+#[test]
+fn stops_once_precise_enough() {
+    let mut calculator = calculator_for(Game::Holdem, &ACES_AND_KINGS, "", 1000 * 1000);
+    calculator.num_threads = 2;
+    calculator.precision = Some(Precision::create(0.01, 0.95));
+    assert!(calculator.stops_early());
+    let result = calculator.run().unwrap();
+
+    // About 6500 hands are needed, but not fewer than the minimum.
+    assert!(result.num_sims >= MIN_SIMS_FOR_PRECISION && result.num_sims < 20 * 1000, "{}", result.num_sims);
+    assert_eq!(0, result.num_sims % SIMS_PER_MERGE);
+    let total: i32 = result.outcomes.values().map(|stats| stats.total_events()).sum();
+    assert_eq!(result.num_sims, total);
+    assert!(result.is_precise_enough(&Precision::create(0.01, 0.95)));
+}
+
+#[test]
+fn stops_at_the_time_limit() {
+    let mut calculator = calculator_for(Game::Holdem, &ACES_AND_KINGS, "", i32::MAX);
+    calculator.time_limit = Some(Duration::from_millis(100));
+    let result = calculator.run().unwrap();
+    assert!(result.num_sims > 0 && result.num_sims < i32::MAX);
+
+    // Still never more than num_sims.
+    calculator.num_sims = 2500;
+    calculator.time_limit = Some(Duration::from_secs(60));
+    assert_eq!(2500, calculator.run().unwrap().num_sims);
+}
+
+#[test]
+fn precision_must_be_positive() {
+    let mut calculator = calculator_for(Game::Holdem, &ACES_AND_KINGS, "", 1000);
+    calculator.precision = Some(Precision::create(0f64, 0.95));
+    assert_eq!(Some(SimulationError::InvalidPrecision), calculator.run().err());
+}
+
+fn seeded_run(seed: u64) -> SimulationResult {
+    let mut calculator = calculator_for(Game::Holdem, &["AA,KK", "AKs,QQ+"], "", 3001);
+    calculator.num_random_opponents = 1;
+    calculator.num_threads = 3;
+    calculator.seed = Some(seed);
+    calculator.run().unwrap()
+}
+
+#[test]
+fn seeded_runs_repeat_exactly() {
+    let result = seeded_run(42);
+    let same_result = seeded_run(42);
+    for (winners, stats) in &result.outcomes {
+        assert_eq!(stats.events, same_result.outcomes[winners].events);
+    }
+    assert_eq!(result.outcomes.len(), same_result.outcomes.len());
+    let pots_won: Vec<u64> = result.player_equities().iter().map(|equity| equity.pots_won.to_bits()).collect();
+    let same_pots_won: Vec<u64> = same_result.player_equities().iter().map(|equity| equity.pots_won.to_bits()).collect();
+    assert_eq!(pots_won, same_pots_won);
+
+    let other_result = seeded_run(43);
+    assert!(result.outcomes.iter().any(|(winners, stats)| other_result.outcomes.get(winners).map(|other_stats| other_stats.events) != Some(stats.events)));
+}
+
+#[test]
+fn seeds_never_expand_to_zero() {
+    for seed in 0..100 {
+        assert!(expand_seed(seed) != [0; 4]);
+    }
+    assert!(expand_seed(0) != expand_seed(1));
+}
+
+#[test]
+fn winners_are_a_bit_per_player() {
+    let (winners, best) = find_winners(vec![Some(3), None, Some(5), Some(5), Some(1)].into_iter());
+    assert_eq!(0b1100, winners);
+    assert_eq!(Some(5), best);
+    assert_eq!(vec![2, 3], winner_indices(winners));
+
+    let (no_winners, no_best) = find_winners(vec![None::<i32>, None].into_iter());
+    assert_eq!(0, no_winners);
+    assert_eq!(None, no_best);
+    assert!(winner_indices(no_winners).is_empty());
+}
+
+#[test]
+fn needs_a_simulation() {
+    let mut calculator = calculator_for(Game::Holdem, &ACES_AND_KINGS, "", 0);
+    assert_eq!(Some(SimulationError::NoSimulations), calculator.run().err());
+    calculator.num_sims = -5;
+    assert_eq!(Some(SimulationError::NoSimulations), calculator.run().err());
+}
+
+#[test]
+fn oversized_boards_have_no_completions() {
+    let calculator = calculator_for(Game::Holdem, &ACES_AND_KINGS, "2c7d9hJc4s5s", 10);
+    assert_eq!(0, calculator.num_board_completions());
+    assert_eq!(Some(SimulationError::TooManyBoardCards{allowed: 5, found: 6}), calculator.run().err());
+}